@@ -0,0 +1,64 @@
+/// Represents a university building (a pavilion or a named building, e.g. the library).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Building {
+    /// The code of the building, as written in spreadsheets (e.g. `3`, `КМЦ`).
+    pub code: &'static str,
+    /// The full name of the building.
+    pub name: &'static str,
+    /// Lowercase aliases of the building used in spreadsheets, besides its code.
+    pub aliases: &'static [&'static str],
+}
+
+/// The registry of university buildings.
+///
+/// Numbered pavilions hold rooms written as `<pavilion>-<room>` (e.g. `3-205`),
+/// while named buildings are written by their code or aliases only.
+pub const BUILDINGS: &[Building] = &[
+    Building { code: "1", name: "Корпус 1", aliases: &[] },
+    Building { code: "2", name: "Корпус 2", aliases: &[] },
+    Building { code: "3", name: "Корпус 3", aliases: &[] },
+    Building { code: "4", name: "Корпус 4", aliases: &[] },
+    Building { code: "5", name: "Корпус 5", aliases: &[] },
+    Building { code: "6", name: "Корпус 6", aliases: &[] },
+    Building { code: "7", name: "Корпус 7", aliases: &[] },
+    Building { code: "8", name: "Корпус 8", aliases: &[] },
+    Building { code: "9", name: "Корпус 9", aliases: &[] },
+    Building {
+        code: "КМЦ",
+        name: "Культурно-мистецький центр",
+        aliases: &["кмц"],
+    },
+    Building {
+        code: "Бібліотека",
+        name: "Наукова бібліотека НаУКМА",
+        aliases: &["бібліотека", "бібл.", "бібл", "читальна зала", "читальний зал"],
+    },
+    Building {
+        code: "Спортзал",
+        name: "Спортивний зал",
+        aliases: &["спортзал", "спортивний зал", "с/з", "спорткомплекс"],
+    },
+];
+
+impl Building {
+    /// Finds a building by its code or alias (case-insensitive).
+    pub fn find(name: &str) -> Option<&'static Building> {
+        let name = name.trim().to_lowercase();
+
+        BUILDINGS.iter().find(|building| {
+            building.code.to_lowercase() == name || building.aliases.contains(&name.as_str())
+        })
+    }
+
+    /// Finds a numbered pavilion.
+    pub fn pavilion(number: u8) -> Option<&'static Building> {
+        let code = number.to_string();
+
+        BUILDINGS.iter().find(|building| building.code == code)
+    }
+
+    /// Checks whether the building is a numbered pavilion with rooms.
+    pub fn is_pavilion(&self) -> bool {
+        self.code.parse::<u8>().is_ok()
+    }
+}
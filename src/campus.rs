@@ -0,0 +1,91 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    building::Building,
+    group::Auditorium,
+};
+
+/// Represents a walking path between two buildings of the campus.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Walk {
+    /// The code of the first building (e.g. `3` or `КМЦ`).
+    pub from: String,
+    /// The code of the second building.
+    pub to: String,
+    /// Walking time in minutes. Paths are walkable in both directions.
+    pub minutes: u16,
+}
+
+/// Represents the campus map as a graph of buildings connected by walking paths.
+///
+/// Walking times between buildings without a direct path are estimated
+/// as the shortest route through other buildings. There is no built-in map:
+/// walking times are read from the `[[campus.walks]]` section of the configuration.
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq, Eq)]
+pub struct Campus {
+    /// Walking paths between buildings.
+    pub walks: Vec<Walk>,
+}
+
+impl Campus {
+    /// Checks whether no walking times are known.
+    pub fn is_empty(&self) -> bool {
+        self.walks.is_empty()
+    }
+
+    /// Estimates the walking time between two buildings.
+    ///
+    /// # Arguments
+    ///
+    /// * `from`: The building to walk from.
+    /// * `to`: The building to walk to.
+    ///
+    /// # Returns
+    ///
+    /// The walking time in minutes, or `None` if there is no known path between the buildings.
+    pub fn walking_minutes(&self, from: &Building, to: &Building) -> Option<u16> {
+        if from.code == to.code {
+            return Some(0);
+        }
+
+        // Dijkstra's algorithm over the undirected walking graph
+        let mut distances: BTreeMap<&str, u16> = BTreeMap::from([(from.code, 0)]);
+        let mut queue: BTreeSet<(u16, &str)> = BTreeSet::from([(0, from.code)]);
+
+        while let Some((distance, code)) = queue.pop_first() {
+            if code == to.code {
+                return Some(distance);
+            }
+
+            for walk in &self.walks {
+                let next = if walk.from == code {
+                    walk.to.as_str()
+                } else if walk.to == code {
+                    walk.from.as_str()
+                } else {
+                    continue;
+                };
+
+                let candidate = distance.saturating_add(walk.minutes);
+                if distances.get(next).is_none_or(|known| candidate < *known) {
+                    distances.insert(next, candidate);
+                    queue.insert((candidate, next));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Estimates the walking time between the auditoriums of two lessons.
+    ///
+    /// # Returns
+    ///
+    /// The walking time in minutes, or `None` if any of the lessons is not held in person
+    /// or there is no known path between the buildings.
+    pub fn walking_minutes_between(&self, from: &Auditorium, to: &Auditorium) -> Option<u16> {
+        self.walking_minutes(from.building()?, to.building()?)
+    }
+}
@@ -0,0 +1,318 @@
+use std::path::PathBuf;
+use clap::{Parser, Subcommand, ValueEnum, Args as ClapArgs, CommandFactory, error::ErrorKind};
+
+use naukma_schedule::{
+    campus::Campus,
+    config::Config,
+    exam::Date,
+    metadata::{AcademicYear, Level, Metadata, Term},
+    profile::Profile,
+    error::ScheduleResult,
+    render::text::TextFormat,
+    schedule::{Schedule, SpecialityName, MetaOverrides},
+    source::ScheduleSource,
+    timetable::{Selection, DisciplineChoice, Timetable},
+};
+
+/// The command-line arguments parsing structure.
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+pub struct Args {
+    /// The list of file paths to university schedule spreadsheets (`.xlsx`, `.xls`, `.ods` or `.csv`)
+    /// or glob patterns matching them (e.g. `2024-1/**/*.xlsx`).
+    #[arg(short, long, global=true, num_args=1..)]
+    pub files: Vec<PathBuf>,
+    /// Directories to search for spreadsheets recursively.
+    /// Lock files (`~$...xlsx`) and other files are skipped.
+    #[arg(long, global=true)]
+    pub dir: Vec<PathBuf>,
+    /// The faculty name to use instead of the one detected from
+    /// file names or title rows.
+    #[arg(long, global=true)]
+    pub faculty: Option<String>,
+    /// The speciality to use instead of the one detected from
+    /// file names or title rows.
+    #[arg(long, global=true)]
+    pub speciality: Option<SpecialityName>,
+    /// The academic year of the schedule (e.g. `2024-2025`), instead of the one detected from
+    /// file names, title rows or the configuration.
+    #[arg(long, global=true)]
+    pub academic_year: Option<AcademicYear>,
+    /// The trimester of the schedule: `осінній`, `весняний` or `літній` (also `autumn`, `spring`, `summer`).
+    #[arg(long, global=true)]
+    pub term: Option<Term>,
+    /// The year of study of the schedule.
+    #[arg(long, global=true)]
+    pub course: Option<u8>,
+    /// The level of study of the schedule: `бакалаврат` or `магістратура` (also `bachelor`, `master`).
+    #[arg(long, global=true)]
+    pub level: Option<Level>,
+    /// The path to a TOML or JSON configuration file (e.g. with the campus map).
+    #[arg(long, global=true)]
+    pub config: Option<PathBuf>,
+    /// The path to a TOML or JSON student profile with personal enrolments.
+    /// Selects the profile's lessons unless overridden with selection flags.
+    #[arg(long, global=true)]
+    pub profile: Option<PathBuf>,
+    /// Watch the input files, parsing them and performing the action again on every change.
+    #[arg(long, global=true)]
+    pub watch: bool,
+    /// Skip the files which cannot be parsed, reporting their errors, instead of failing.
+    #[arg(long, global=true)]
+    pub lenient: bool,
+    /// Merge identical rows of a discipline and rows of a lesson split by weeks (e.g. `1-5` and `6-13`),
+    /// printing every merge. Can also be switched on with `merge_duplicates = true` in the config.
+    #[arg(long, global=true)]
+    pub merge_duplicates: bool,
+    /// Write the generation time into the schedule metadata. Can also be switched on
+    /// with `timestamp = true` in the config; `SOURCE_DATE_EPOCH` is used instead of the current time if set.
+    #[arg(long, global=true)]
+    pub timestamp: bool,
+    /// The action to perform with the parsed schedule.
+    /// Writes `schedule.json` if not specified.
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Actions available for the parsed schedule.
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Renders a static HTML timetable.
+    Html {
+        /// The path of the HTML file to write.
+        #[arg(short, long, default_value = "schedule.html")]
+        output: PathBuf,
+        /// Lessons to put into the timetable.
+        #[command(flatten)]
+        selection: SelectionArgs,
+    },
+    /// Prints a compact Markdown or plain-text timetable for posting to chats.
+    Text {
+        /// The output text format.
+        #[arg(long, value_enum, default_value_t)]
+        format: TextFormat,
+        /// The path of the file to write. Prints to the standard output if not specified.
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Lessons to put into the timetable.
+        #[command(flatten)]
+        selection: SelectionArgs,
+    },
+    /// Exports exam sessions as an HTML table or an iCalendar file
+    /// and reports conflicts between exams.
+    Exams {
+        /// The output format.
+        #[arg(long, value_enum, default_value_t)]
+        format: ExamsFormat,
+        /// The path of the file to write.
+        #[arg(short, long)]
+        output: PathBuf,
+        /// Exams to export.
+        #[command(flatten)]
+        selection: SelectionArgs,
+    },
+    /// Writes a normalized xlsx workbook per faculty, which can be parsed back.
+    Xlsx {
+        /// The directory to write workbooks to.
+        #[arg(short, long, default_value = ".")]
+        output_dir: PathBuf,
+    },
+    /// Exports lessons as an iCalendar file with an event for every lesson occurrence.
+    Ics {
+        /// The path of the file to write.
+        #[arg(short, long, default_value = "schedule.ics")]
+        output: PathBuf,
+        /// Any day of the first studying week. Taken from the profile if not specified.
+        #[arg(long)]
+        semester_start: Option<Date>,
+        /// Lessons to export.
+        #[command(flatten)]
+        selection: SelectionArgs,
+    },
+    /// Reports overlapping lessons, too short breaks and exam conflicts of a selection.
+    Conflicts {
+        /// Lessons and exams to check.
+        #[command(flatten)]
+        selection: SelectionArgs,
+    },
+    /// Serves the schedule with a local HTTP JSON API, reloading it when the input files change.
+    Serve {
+        /// The address to listen on.
+        #[arg(long, default_value = "127.0.0.1:8080")]
+        address: String,
+        /// The directory with student profiles served as iCalendar feeds at `/profiles/<name>.ics`.
+        #[arg(long)]
+        profiles: Option<PathBuf>,
+    },
+    /// Prints the parse report: every worksheet row which was not parsed, with the reason.
+    Report,
+    /// Writes the normalized schedule JSON, where lessons shared by specialities are stored once.
+    Normalized {
+        /// The path of the JSON file to write.
+        #[arg(short, long, default_value = "schedule.normalized.json")]
+        output: PathBuf,
+    },
+    /// Prints the lessons attended by several specialities together.
+    Shared,
+    /// Checks the schedule with the lint rules switched on in the config, failing on errors.
+    Lint,
+    /// Prints the next lesson.
+    Next {
+        /// The moment to search from in `dd.mm.yyyy HH:MM` format. Defaults to the current local time.
+        #[arg(long)]
+        at: Option<String>,
+        /// Any day of the first studying week. Taken from the profile if not specified.
+        #[arg(long)]
+        semester_start: Option<Date>,
+        /// Lessons to search in.
+        #[command(flatten)]
+        selection: SelectionArgs,
+    },
+}
+
+/// Output formats of exam sessions.
+#[derive(ValueEnum, Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExamsFormat {
+    /// Standalone HTML page with a table of exams.
+    #[default]
+    Html,
+    /// iCalendar file for calendar applications.
+    Ics,
+}
+
+/// Command-line arguments selecting lessons for a timetable.
+#[derive(ClapArgs, Debug)]
+pub struct SelectionArgs {
+    /// Select lessons of the faculty with the given name only.
+    #[arg(long)]
+    pub select_faculty: Option<String>,
+    /// Select lessons of the speciality with the given name only.
+    #[arg(long)]
+    pub select_speciality: Option<SpecialityName>,
+    /// Select lessons taught during the given week only.
+    #[arg(long)]
+    pub week: Option<u8>,
+    /// Personally chosen disciplines in `<name part>[:<group>,<group>...]` format.
+    /// Example: `Системне програмування:2`.
+    #[arg(short, long)]
+    pub discipline: Vec<DisciplineChoice>,
+    /// Choose a single seminar group of every chosen discipline without explicit groups,
+    /// preferring compact days with short gaps and walks.
+    #[arg(long)]
+    pub compact: bool,
+}
+
+impl Args {
+    /// Parses the command-line arguments, checking that directories and glob patterns can be resolved.
+    /// Exits with a usage error if no schedule files are found.
+    pub fn parse_checked() -> Self {
+        let args = Args::parse();
+
+        match args.source().resolve() {
+            Ok(files) if files.is_empty() => Args::command()
+                .error(ErrorKind::MissingRequiredArgument, "at least one schedule file must be passed with `--files` or found with `--dir`")
+                .exit(),
+            Ok(_) => args,
+            Err(e) => Args::command().error(ErrorKind::ValueValidation, format!("{:#}", anyhow::Error::from(e))).exit(),
+        }
+    }
+
+    /// Collects the inputs of the schedule: files, glob patterns, directories and metadata flags.
+    pub fn source(&self) -> ScheduleSource {
+        ScheduleSource {
+            files: self.files.clone(),
+            dirs: self.dir.clone(),
+            overrides: self.overrides(),
+            metadata: self.metadata(),
+            lenient: self.lenient,
+            merge_duplicates: self.merge_duplicates,
+            timestamp: self.timestamp,
+        }
+    }
+
+    /// Collects the faculty metadata set explicitly with command-line flags.
+    pub fn overrides(&self) -> MetaOverrides {
+        MetaOverrides {
+            faculty: self.faculty.clone(),
+            speciality: self.speciality,
+        }
+    }
+
+    /// Returns the schedule metadata set with flags.
+    pub fn metadata(&self) -> Metadata {
+        Metadata {
+            academic_year: self.academic_year,
+            term: self.term,
+            course: self.course,
+            level: self.level,
+            ..Default::default()
+        }
+    }
+
+    /// Reads the student profile file if passed.
+    pub fn profile(&self) -> ScheduleResult<Option<Profile>> {
+        self.profile.as_ref().map(Profile::from_path).transpose()
+    }
+
+    /// Reads the configuration file if passed, or returns the default configuration.
+    pub fn config(&self) -> ScheduleResult<Config> {
+        match &self.config {
+            Some(path) => Config::from_path(path),
+            None => Ok(Config::default()),
+        }
+    }
+}
+
+impl SelectionArgs {
+    /// Converts the arguments into a timetable selection.
+    /// Selection flags take precedence over the student profile.
+    pub fn selection(&self, profile: Option<&Profile>) -> Selection {
+        let base = profile.map(Profile::selection).unwrap_or_default();
+
+        Selection {
+            faculty: self.select_faculty.clone().or(base.faculty),
+            speciality: self.select_speciality.or(base.speciality),
+            week: self.week,
+            disciplines: if self.discipline.is_empty() { base.disciplines } else { self.discipline.clone() },
+        }
+    }
+
+    /// Builds a timetable of the selected lessons, compacting it if requested.
+    pub fn timetable<'a>(&self, schedule: &'a Schedule, profile: Option<&Profile>, campus: &Campus) -> Timetable<'a> {
+        let selection = self.selection(profile);
+
+        if self.compact {
+            Timetable::compact(schedule, &selection, campus)
+        } else {
+            Timetable::new(schedule, &selection)
+        }
+    }
+
+    /// Builds a human-readable title describing the selection.
+    pub fn title(&self, profile: Option<&Profile>) -> String {
+        let selection = self.selection(profile);
+        let mut parts = vec![];
+
+        if let Some(faculty) = &selection.faculty {
+            parts.push(faculty.clone());
+        }
+
+        if let Some(speciality) = selection.speciality {
+            parts.push(speciality.to_string());
+        }
+
+        if !selection.disciplines.is_empty() {
+            parts.push("Особистий розклад".to_owned());
+        }
+
+        if let Some(week) = self.week {
+            parts.push(format!("Тиждень {week}"));
+        }
+
+        if parts.is_empty() {
+            "Розклад".to_owned()
+        } else {
+            parts.join(" · ")
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use std::path::Path;
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    campus::Campus,
+    group::Weeks,
+    lint::LintConfig,
+    metadata::Metadata,
+    error::ScheduleResult,
+};
+
+/// Represents the user configuration of the schedule tools, read from a TOML or JSON file.
+///
+/// Every section is optional and falls back to its defaults.
+///
+/// ```toml
+/// semester_weeks = 14
+/// merge_duplicates = true
+///
+/// [metadata]
+/// academic_year = "2024-2025"
+/// term = "осінній"
+///
+/// [[campus.walks]]
+/// from = "1"
+/// to = "3"
+/// minutes = 3
+///
+/// [lint.rules]
+/// late-saturday = false
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct Config {
+    /// The number of studying weeks of the semester, bounding week patterns
+    /// such as `непарні` and checked by the `weeks-beyond-semester` lint rule.
+    pub semester_weeks: u8,
+    /// Whether duplicate rows of a discipline are merged into one lesson (see `Discipline::merge_duplicates`).
+    /// Off by default, so every row of the spreadsheets is kept.
+    pub merge_duplicates: bool,
+    /// Whether the generation time is written into the schedule metadata.
+    /// Off by default, so the output is the same for the same input files.
+    pub timestamp: bool,
+    /// The campus map used to estimate walking times between buildings.
+    /// Empty by default, so short breaks are not checked.
+    pub campus: Campus,
+    /// The settings of the schedule checks.
+    pub lint: LintConfig,
+    /// The metadata of the schedule, taking precedence over the metadata detected from spreadsheets.
+    pub metadata: Metadata,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            semester_weeks: Weeks::DEFAULT_SEMESTER_WEEKS,
+            merge_duplicates: false,
+            timestamp: false,
+            campus: Campus::default(),
+            lint: LintConfig::default(),
+            metadata: Metadata::default(),
+        }
+    }
+}
+
+impl Config {
+    /// Reads the configuration from a file.
+    ///
+    /// Files with the `.json` extension are read as JSON, any others as TOML.
+    ///
+    /// # Arguments
+    ///
+    /// * `path`: The path to the configuration file.
+    ///
+    /// # Returns
+    ///
+    /// A `ScheduleResult` containing the read configuration.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> ScheduleResult<Self> {
+        read_document(path.as_ref())
+    }
+}
+
+/// Reads a document from a JSON file (with the `.json` extension) or a TOML file (any other extension).
+pub(crate) fn read_document<T: DeserializeOwned>(path: &Path) -> ScheduleResult<T> {
+    let content = std::fs::read_to_string(path)?;
+
+    let is_json = path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("json"));
+
+    if is_json {
+        Ok(serde_json::from_str(&content)?)
+    } else {
+        Ok(toml::from_str(&content)?)
+    }
+}
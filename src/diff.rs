@@ -0,0 +1,223 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+};
+
+use crate::schedule::*;
+
+/// Represents counts of the schedule elements, printed after parsing.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Summary {
+    /// The number of faculties.
+    pub faculties: usize,
+    /// The number of specialities of all faculties.
+    pub specialities: usize,
+    /// The number of disciplines of all specialities.
+    pub disciplines: usize,
+    /// The number of lesson groups of all disciplines.
+    pub lessons: usize,
+    /// The number of exams of all exam sessions.
+    pub exams: usize,
+}
+
+impl Summary {
+    /// Counts the elements of the schedule.
+    pub fn of(schedule: &Schedule) -> Self {
+        let mut summary = Summary {
+            faculties: schedule.faculties.len(),
+            exams: schedule.exam_sessions.iter().map(|session| session.exams.len()).sum(),
+            ..Default::default()
+        };
+
+        for faculty in &schedule.faculties {
+            summary.specialities += faculty.specialities.len();
+
+            for speciality in faculty.specialities.values() {
+                summary.disciplines += speciality.disciplines.len();
+                summary.lessons += speciality.disciplines.values().map(|d| d.groups.len()).sum::<usize>();
+            }
+        }
+
+        summary
+    }
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} faculties, {} specialities, {} disciplines, {} lessons, {} exams",
+            self.faculties, self.specialities, self.disciplines, self.lessons, self.exams,
+        )
+    }
+}
+
+/// Represents a discipline of a faculty speciality, identifying it between schedules.
+pub type DisciplineKey = (String, SpecialityName, String);
+
+/// Represents the differences of disciplines between two parsed schedules.
+#[derive(Default, Clone, Debug, PartialEq)]
+pub struct ScheduleDiff {
+    /// Disciplines missing from the old schedule.
+    pub added: Vec<DisciplineKey>,
+    /// Disciplines missing from the new schedule.
+    pub removed: Vec<DisciplineKey>,
+    /// Disciplines with changed lessons, with the old and new number of lessons.
+    pub changed: Vec<(DisciplineKey, usize, usize)>,
+    /// The number of exams in the old and new schedule.
+    pub exams: (usize, usize),
+}
+
+impl ScheduleDiff {
+    /// Compares two schedules.
+    ///
+    /// # Arguments
+    ///
+    /// * `old`: The previous schedule.
+    /// * `new`: The current schedule.
+    ///
+    /// # Returns
+    ///
+    /// The added, removed and changed disciplines, sorted by faculty, speciality and name.
+    pub fn between(old: &Schedule, new: &Schedule) -> Self {
+        let old_disciplines = Self::disciplines(old);
+        let new_disciplines = Self::disciplines(new);
+        let mut diff = ScheduleDiff {
+            exams: (Summary::of(old).exams, Summary::of(new).exams),
+            ..Default::default()
+        };
+
+        for (key, discipline) in &new_disciplines {
+            match old_disciplines.get(key) {
+                None => diff.added.push(key.clone()),
+                Some(old) if old.groups != discipline.groups => {
+                    diff.changed.push((key.clone(), old.groups.len(), discipline.groups.len()));
+                },
+                Some(_) => {},
+            }
+        }
+
+        diff.removed = old_disciplines
+            .keys()
+            .filter(|key| !new_disciplines.contains_key(*key))
+            .cloned()
+            .collect();
+
+        diff
+    }
+
+    /// Checks whether the schedules are equal.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty() && self.exams.0 == self.exams.1
+    }
+
+    /// Collects the disciplines of every faculty speciality of the schedule.
+    fn disciplines(schedule: &Schedule) -> BTreeMap<DisciplineKey, &Discipline> {
+        let mut disciplines = BTreeMap::new();
+
+        for faculty in &schedule.faculties {
+            for (speciality, spec) in &faculty.specialities {
+                for (name, discipline) in &spec.disciplines {
+                    disciplines.insert((faculty.name.clone(), *speciality, name.clone()), discipline);
+                }
+            }
+        }
+
+        disciplines
+    }
+}
+
+impl Display for ScheduleDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "No changes");
+        }
+
+        for (faculty, speciality, discipline) in &self.added {
+            writeln!(f, "+ {faculty} / {speciality} / {discipline}")?;
+        }
+
+        for (faculty, speciality, discipline) in &self.removed {
+            writeln!(f, "- {faculty} / {speciality} / {discipline}")?;
+        }
+
+        for ((faculty, speciality, discipline), old, new) in &self.changed {
+            writeln!(f, "~ {faculty} / {speciality} / {discipline} ({old} → {new} lessons)")?;
+        }
+
+        if self.exams.0 != self.exams.1 {
+            writeln!(f, "~ exams: {} → {}", self.exams.0, self.exams.1)?;
+        }
+
+        writeln!(
+            f,
+            "{} added, {} removed, {} changed disciplines",
+            self.added.len(), self.removed.len(), self.changed.len(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    /// Parses a schedule of the Економіка speciality from CSV rows below the header.
+    fn schedule(rows: &str) -> Schedule {
+        let csv = format!("День;Час;Дисципліна;Група;Тижні;Аудиторія\n{rows}");
+        let meta = FacultyMeta { name: "ФЕН".to_owned(), speciality: Some(SpecialityName::Economics) };
+
+        Schedule::from_sources([(Cursor::new(csv), meta)]).unwrap()
+    }
+
+    /// Lessons of the old schedule.
+    const OLD: &str = "\
+        Понеділок;08:30-09:50;Алгебра;лекція;1-13;1-225\n\
+        Понеділок;10:00-11:20;Алгебра;1;1-13;1-225\n\
+        Вівторок;08:30-09:50;Статистика;1;1-13;3-205\n";
+
+    #[test]
+    fn counts_schedule_elements() {
+        assert_eq!(Summary::of(&schedule(OLD)).to_string(), "1 faculties, 1 specialities, 2 disciplines, 3 lessons, 0 exams");
+    }
+
+    #[test]
+    fn equal_schedules_have_no_changes() {
+        let diff = ScheduleDiff::between(&schedule(OLD), &schedule(OLD));
+
+        assert!(diff.is_empty());
+        assert_eq!(diff.to_string(), "No changes\n");
+    }
+
+    #[test]
+    fn lists_added_removed_and_changed_disciplines() {
+        let new = schedule(
+            "Понеділок;08:30-09:50;Алгебра;лекція;1-13;1-225\n\
+             Понеділок;10:00-11:20;Алгебра;1;1-13;1-225\n\
+             Середа;10:00-11:20;Алгебра;2;1-13;1-225\n\
+             Четвер;11:40-13:00;Мікроекономіка;лекція;1-13;КМЦ\n",
+        );
+        let diff = ScheduleDiff::between(&schedule(OLD), &new);
+        let key = |discipline: &str| ("ФЕН".to_owned(), SpecialityName::Economics, discipline.to_owned());
+
+        assert_eq!(diff.added, [key("Мікроекономіка")]);
+        assert_eq!(diff.removed, [key("Статистика")]);
+        assert_eq!(diff.changed, [(key("Алгебра"), 2, 3)]);
+        assert_eq!(diff.to_string(), "\
++ ФЕН / Економіка / Мікроекономіка
+- ФЕН / Економіка / Статистика
+~ ФЕН / Економіка / Алгебра (2 → 3 lessons)
+1 added, 1 removed, 1 changed disciplines
+");
+    }
+
+    #[test]
+    fn lessons_moved_to_other_rooms_are_changes() {
+        let new = schedule(&OLD.replace("3-205", "3-206"));
+        let diff = ScheduleDiff::between(&schedule(OLD), &new);
+
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].0.2, "Статистика");
+    }
+}
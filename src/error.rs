@@ -1,30 +1,65 @@
 use thiserror::Error;
-use validator::ValidationErrors;
+use validator::{ValidationError, ValidationErrors};
 
 /// Represents custom error types for the university schedule parser program.
+///
+/// Errors of other crates are kept as sources under a short message, so `{:#}` of `anyhow`
+/// and `ScheduleError::describe` show their details once.
 #[derive(Debug, Error)]
 pub enum ScheduleError {
     /// Input/Output error while reading or writing files.
     #[error("Input/Output error")]
     IoError(#[from] std::io::Error),
-    /// Error related to processing .xlsx documents.
-    #[error("Cannot process .xlsx document")]
+    /// Error related to processing spreadsheets (`.xlsx`, `.xls`, `.xlsb` or `.ods`).
+    #[error("Cannot process spreadsheet")]
     XlsxError(#[from] calamine::Error),
+    /// Error related to processing .csv documents.
+    #[error("Cannot process .csv document")]
+    CsvError(#[from] csv::Error),
+    /// Error related to writing .xlsx documents.
+    #[error("Cannot write .xlsx document")]
+    XlsxWriteError(#[from] rust_xlsxwriter::XlsxError),
+    /// Error related to reading JSON documents.
+    #[error("Cannot process JSON document")]
+    JsonError(#[from] serde_json::Error),
+    /// Error related to reading TOML documents.
+    #[error("Cannot process TOML document")]
+    TomlError(#[from] toml::de::Error),
     /// Validation error indicating that data does not meet expected criteria.
-    #[error("Validation error")]
-    ValidationError(#[from] ValidationErrors),
+    /// The failed validations are described in the message, so they are not a source.
+    #[error("Validation error: {}", describe_validation(.0))]
+    ValidationError(ValidationErrors),
+    /// Validation error of a value parsed from a spreadsheet cell or an argument.
+    #[error("{} in `{input}`", describe_validation(errors))]
+    InvalidValue {
+        /// The text the value was parsed from.
+        input: String,
+        /// The failed validations.
+        errors: ValidationErrors,
+    },
+    /// Error of a single input file or directory.
+    #[error("Cannot read `{path}`")]
+    InFile {
+        /// The path of the file or directory.
+        path: String,
+        /// The error of the file.
+        source: Box<ScheduleError>,
+    },
     
     /// Error indicating an invalid auditorium format.
-    #[error("Invalid auditorium: `{0}`.\nExamples: `3-205`, `1-313`, `КМЦ`, `Д`, `д`, `Дистанційно`")]
+    #[error("Invalid auditorium: `{0}`.\nExamples: `3-205`, `ауд. 1-225а`, `КМЦ`, `Бібліотека`, `Д`, `online (Zoom)`, `6-204 + Д`, `тба`")]
     InvalidAuditorium(String),
     /// Error indicating an invalid study weeks format.
-    #[error("Invalid study weeks format: `{0}`.\nExamples: `1-13`, `2,3,7,9`, `1`, `1,3-8,10,12-16`")]
+    #[error("Invalid study weeks format: `{0}`.\nExamples: `1-13`, `2,3,7,9`, `1`, `1,3-8,10,12-16`, `непарні`, `парні до 12`, `щотижня`, `кожен другий тиждень з 2`")]
     InvalidWeeksFormat(String),
     /// Error indicating an invalid time format.
     #[error("Invalid time format: `{0}`.\nExamples: `13:25`, `06.45`")]
     InvalidTimeFormat(String),
+    /// Error indicating an invalid date format.
+    #[error("Invalid date: `{0}`.\nExamples: `15.01.2024`, `15/01/24`, `2024-01-15`")]
+    InvalidDate(String),
     /// Error indicating an invalid lesson time format.
-    #[error("Invalid lesson time: `{0}`.\nExamples: `08:30-09:50`, `11.40-13.00`")]
+    #[error("Invalid lesson time: `{0}`.\nExamples: `08:30-09:50`, `11.40-13.00`, `10:00` (start time only)")]
     InvalidLessonTime(String),
     /// Error indicating an invalid lesson type.
     #[error("Invalid lesson type passed: {0}")]
@@ -32,10 +67,169 @@ pub enum ScheduleError {
     /// Error indicating an invalid day of the week.
     #[error("Wrong day of the week passed: {0}")]
     InvalidDayOfWeek(String),
+    /// Error indicating a spreadsheet path without a valid UTF-8 file name.
+    #[error("Invalid schedule file path (file name must be valid UTF-8): `{0}`")]
+    InvalidPath(String),
+    /// Error indicating an invalid glob pattern of input files.
+    #[error("Invalid file pattern: `{0}`.\nExamples: `schedules/*.xlsx`, `2024-1/**/*.xlsx`")]
+    InvalidPattern(String),
+    /// Error indicating an invalid academic year, term or level of study.
+    #[error("Invalid schedule metadata: `{0}`.\nExamples: `2024-2025`, `осінній`, `spring`, `бакалаврат`, `master`")]
+    InvalidMetadata(String),
     /// Error indicating that a speciality does not exist.
     #[error("No such speciality: {0}")]
     InvalidSpeciality(String),
+    /// Error indicating that a student profile does not match the schedule.
+    #[error("Student profile does not match the schedule:\n{0}")]
+    InvalidProfile(String),
+}
+
+impl ScheduleError {
+    /// Creates an error of a value which failed validation, keeping the text it was parsed from.
+    ///
+    /// # Arguments
+    ///
+    /// * `input`: The text the value was parsed from (e.g. `13:75`).
+    /// * `errors`: The failed validations.
+    ///
+    /// # Returns
+    ///
+    /// The error with a message such as ``minutes must be 0..59, got 75 in `13:75` ``.
+    pub fn invalid_value(input: &str, errors: ValidationErrors) -> Self {
+        ScheduleError::InvalidValue { input: input.to_owned(), errors }
+    }
+
+    /// Creates an error of an input file or directory, keeping its path.
+    pub fn in_file(path: &std::path::Path, error: ScheduleError) -> Self {
+        ScheduleError::InFile { path: path.display().to_string(), source: Box::new(error) }
+    }
+
+    /// Describes the error with its sources, e.g. ``Cannot read `a.xlsx`: Input/Output error: No such file``,
+    /// for messages which are not printed with `anyhow` (e.g. server responses).
+    pub fn describe(&self) -> String {
+        let mut description = self.to_string();
+        let mut source = std::error::Error::source(self);
+
+        while let Some(error) = source {
+            description.push_str(": ");
+            description.push_str(&error.to_string());
+            source = error.source();
+        }
+
+        description
+    }
+
+    /// Returns the machine-readable code of the error, which frontends can translate.
+    pub fn code(&self) -> &'static str {
+        use ScheduleError::*;
+
+        match self {
+            IoError(_) => "io",
+            XlsxError(_) => "spreadsheet",
+            CsvError(_) => "csv",
+            XlsxWriteError(_) => "xlsx-write",
+            JsonError(_) => "json",
+            TomlError(_) => "toml",
+            ValidationError(_) => "validation",
+            InvalidValue { .. } => "invalid-value",
+            InFile { source, .. } => source.code(),
+            InvalidAuditorium(_) => "invalid-auditorium",
+            InvalidWeeksFormat(_) => "invalid-weeks",
+            InvalidTimeFormat(_) => "invalid-time",
+            InvalidDate(_) => "invalid-date",
+            InvalidLessonTime(_) => "invalid-lesson-time",
+            InvalidLessonType(_) => "invalid-lesson-type",
+            InvalidDayOfWeek(_) => "invalid-day",
+            InvalidPath(_) => "invalid-path",
+            InvalidPattern(_) => "invalid-pattern",
+            InvalidMetadata(_) => "invalid-metadata",
+            InvalidSpeciality(_) => "invalid-speciality",
+            InvalidProfile(_) => "invalid-profile",
+        }
+    }
+}
+
+impl From<ValidationErrors> for ScheduleError {
+    fn from(errors: ValidationErrors) -> Self {
+        ScheduleError::ValidationError(errors)
+    }
+}
+
+/// Describes failed validations, e.g. `minutes must be 0..59, got 75`.
+fn describe_validation(errors: &ValidationErrors) -> String {
+    let mut fields: Vec<(&str, &Vec<ValidationError>)> = errors
+        .field_errors()
+        .into_iter()
+        .collect();
+    fields.sort_by_key(|(field, _)| *field);
+
+    let descriptions: Vec<String> = fields
+        .into_iter()
+        .flat_map(|(field, errors)| errors.iter().map(move |error| describe_field(field, error)))
+        .collect();
+
+    descriptions.join(", ")
+}
+
+/// Describes a failed validation of a field.
+fn describe_field(field: &str, error: &ValidationError) -> String {
+    let param = |name: &str| error.params.get(name).map(|value| match value.as_f64() {
+        Some(number) if number.fract() == 0.0 => (number as i64).to_string(),
+        _ => value.to_string(),
+    });
+
+    if let Some(message) = &error.message {
+        return message.to_string();
+    }
+
+    match (error.code.as_ref(), param("min"), param("max"), param("value")) {
+        ("range", Some(min), Some(max), Some(value)) => format!("{field} must be {min}..{max}, got {value}"),
+        (code, ..) => format!("{field} is invalid ({code})"),
+    }
 }
 
 /// A type alias for results that may return a `ScheduleError`.
-pub type ScheduleResult<T> = Result<T, ScheduleError>;
\ No newline at end of file
+pub type ScheduleResult<T> = Result<T, ScheduleError>;
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, path::Path, str::FromStr};
+
+    use super::*;
+    use crate::group::Time;
+
+    #[test]
+    fn describes_invalid_values() {
+        let error = Time::from_str("13:75").unwrap_err();
+
+        assert_eq!(error.to_string(), "minutes must be 0..59, got 75 in `13:75`");
+        assert_eq!(error.describe(), error.to_string());
+        assert!(error.source().is_none());
+        assert_eq!(error.code(), "invalid-value");
+    }
+
+    #[test]
+    fn describes_sources_once() {
+        let io = std::io::Error::new(std::io::ErrorKind::NotFound, "No such file");
+        let error = ScheduleError::in_file(Path::new("a.xlsx"), io.into());
+
+        assert_eq!(error.to_string(), "Cannot read `a.xlsx`");
+        assert_eq!(error.describe(), "Cannot read `a.xlsx`: Input/Output error: No such file");
+        assert_eq!(format!("{:#}", anyhow::Error::from(error)), "Cannot read `a.xlsx`: Input/Output error: No such file");
+    }
+
+    #[test]
+    fn describes_spreadsheet_errors() {
+        let error = ScheduleError::from(calamine::Error::Msg("Cannot detect file format"));
+
+        assert_eq!(error.describe(), "Cannot process spreadsheet: Cannot detect file format");
+        assert_eq!(error.code(), "spreadsheet");
+    }
+
+    #[test]
+    fn codes_of_file_errors_are_codes_of_their_sources() {
+        let error = ScheduleError::in_file(Path::new("a.csv"), ScheduleError::InvalidWeeksFormat("1-".to_owned()));
+
+        assert_eq!(error.code(), "invalid-weeks");
+    }
+}
@@ -0,0 +1,551 @@
+use std::fmt::Display;
+use std::path::Path;
+use std::str::FromStr;
+use serde::{Serialize, Deserialize};
+use calamine::{Data, Range};
+use validator::{Validate, ValidationError, ValidationErrors};
+
+use crate::{
+    group::*,
+    schedule::*,
+    macros::impl_serde_display_fromstr,
+    report::{SkipReason, SkippedRow},
+    spreadsheet::{read_worksheets, header_row},
+    error::{ScheduleResult, ScheduleError},
+};
+
+impl_serde_display_fromstr!(Date);
+
+/// Represents a calendar date of an exam.
+///
+/// The `Date` struct can be validated to ensure that the day exists in the given month.
+#[derive(Validate, Default, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[validate(schema(function = "validate_date"))]
+pub struct Date {
+    /// The year component of the date.
+    #[validate(range(min = 2000, max = 2100))]
+    year: u16,
+    /// The month component of the date.
+    #[validate(range(min = 1, max = 12))]
+    month: u8,
+    /// The day component of the date.
+    #[validate(range(min = 1, max = 31))]
+    day: u8,
+}
+
+/// Checks that the day of a date exists in its month.
+fn validate_date(date: &Date) -> Result<(), ValidationError> {
+    if (1..=12).contains(&date.month) && date.day > Date::days_in_month(date.year, date.month) {
+        let mut error = ValidationError::new("day_of_month");
+        error.message = Some(format!("day {} does not exist in month {}", date.day, date.month).into());
+
+        Err(error)
+    } else {
+        Ok(())
+    }
+}
+
+impl Date {
+    /// Creates a new `Date` instance with the specified year, month and day.
+    ///
+    /// # Arguments
+    ///
+    /// * `year`: The year component of the date.
+    /// * `month`: The month component of the date.
+    /// * `day`: The day component of the date.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the created `Date` if it passes validation,
+    /// or validation errors if validation fails.
+    pub fn new(year: u16, month: u8, day: u8) -> Result<Self, ValidationErrors> {
+        let date = Date { year, month, day };
+        date.validate()?;
+
+        Ok(date)
+    }
+
+    /// Creates a new `Date` from an Excel date serial number (days since 1899-12-30).
+    pub fn from_excel_serial(serial: f64) -> Result<Self, ValidationErrors> {
+        // Shift the epoch from 1899-12-30 to 1970-01-01
+        let (year, month, day) = Self::civil_from_days(serial.floor() as i64 - 25569);
+
+        Date::new(year as u16, month, day)
+    }
+
+    /// Returns the year component of the date.
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    /// Returns the month component of the date.
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// Returns the day component of the date.
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// Returns the number of days in a month of a year.
+    pub fn days_in_month(year: u16, month: u8) -> u8 {
+        match month {
+            2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+
+    /// Returns the number of days since 1970-01-01.
+    pub fn days_since_epoch(&self) -> i64 {
+        let year = i64::from(self.year) - i64::from(self.month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let shifted_month = (i64::from(self.month) + 9) % 12;
+        let day_of_year = (153 * shifted_month + 2) / 5 + i64::from(self.day) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+        era * 146097 + day_of_era - 719468
+    }
+
+    /// Returns the date shifted by the given number of days.
+    pub fn add_days(&self, days: i64) -> Self {
+        let (year, month, day) = Self::civil_from_days(self.days_since_epoch() + days);
+
+        Date { year: year as u16, month, day }
+    }
+
+    /// Returns the day of the week of the date, or `None` on Sunday,
+    /// which has no lessons.
+    pub fn weekday(&self) -> Option<Day> {
+        // 1970-01-01 was Thursday
+        Day::ALL.get((self.days_since_epoch() + 3).rem_euclid(7) as usize).copied()
+    }
+
+    /// Returns the Monday of the week containing the date.
+    pub fn monday(&self) -> Self {
+        self.add_days(-(self.days_since_epoch() + 3).rem_euclid(7))
+    }
+
+    /// Converts a number of days since 1970-01-01 into a civil date (year, month, day).
+    fn civil_from_days(days: i64) -> (i64, u8, u8) {
+        let z = days + 719468;
+        let era = z.div_euclid(146097);
+        let day_of_era = z.rem_euclid(146097);
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u8;
+        let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 } as u8;
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+        (year, month, day)
+    }
+}
+
+impl Display for Date {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:02}.{:02}.{}", self.day, self.month, self.year)
+    }
+}
+
+impl FromStr for Date {
+    type Err = ScheduleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let invalid = || ScheduleError::InvalidDate(s.to_owned());
+
+        let elements: Vec<&str> = s.split(['.', '/']).collect();
+        let (day, month, year) = match elements.as_slice() {
+            [day, month, year] => (*day, *month, *year),
+            _ => match s.split('-').collect::<Vec<&str>>().as_slice() {
+                [year, month, day] => (*day, *month, *year),
+                _ => Err(invalid())?,
+            },
+        };
+
+        let day = day.parse::<u8>().map_err(|_| invalid())?;
+        let month = month.parse::<u8>().map_err(|_| invalid())?;
+        let year = match year.parse::<u16>().map_err(|_| invalid())? {
+            short @ 0..=99 => 2000 + short,
+            full => full,
+        };
+
+        Date::new(year, month, day).map_err(|errors| ScheduleError::invalid_value(s, errors))
+    }
+}
+
+/// Represents a single exam (or credit) of an exam session.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Exam {
+    /// The date of the exam.
+    #[serde(rename = "Дата")]
+    pub date: Date,
+    /// The time of the exam.
+    #[serde(rename = "Час")]
+    pub time: LessonTime,
+    /// The full discipline name, as written in the spreadsheet.
+    #[serde(rename = "Дисципліна")]
+    pub discipline: String,
+    /// The groups passing the exam.
+    #[serde(rename = "Група")]
+    pub group: LessonType,
+    /// The auditorium where the exam takes place.
+    #[serde(rename = "Аудиторія")]
+    pub auditorium: Auditorium,
+    /// The specialities passing the exam.
+    #[serde(rename = "Спеціальності")]
+    pub specialities: Vec<SpecialityName>,
+}
+
+/// Represents an exam/credit session of a faculty, parsed from a spreadsheet with
+/// `Дата | Час | Дисципліна | Група | Аудиторія` columns.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ExamSession {
+    /// The name of the faculty.
+    #[serde(rename = "Назва факультету")]
+    pub faculty: String,
+    /// Exams of the session, sorted by date and time.
+    #[serde(rename = "Екзамени")]
+    pub exams: Vec<Exam>,
+    /// Worksheet rows which were not parsed into exams.
+    #[serde(skip)]
+    pub skipped_rows: Vec<SkippedRow>,
+}
+
+impl ExamSession {
+    /// The first header cell of exam session worksheets.
+    pub const HEADER: &'static str = "Дата";
+
+    /// Creates a new `ExamSession` by parsing an exam session spreadsheet file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path`: The path to the spreadsheet file.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the parsed `ExamSession` if successful, or an error if parsing fails.
+    pub fn new(path: &Path) -> ScheduleResult<Self> {
+        let worksheets = read_worksheets(path)?;
+        let meta = FacultyMeta::detect(path, &worksheets, &MetaOverrides::default())?;
+
+        Self::from_worksheets(&worksheets, meta)
+    }
+
+    /// Checks whether a worksheet contains an exam session (its header starts with `Дата`).
+    pub fn is_session_sheet(range: &Range<Data>) -> bool {
+        header_row(range)
+            .and_then(|header| range.rows().nth(header))
+            .is_some_and(|row| matches!(row.first(), Some(Data::String(s)) if s.trim() == Self::HEADER))
+    }
+
+    /// Creates a new `ExamSession` from the worksheets of a spreadsheet.
+    ///
+    /// Only the worksheets with exam session headers are parsed.
+    ///
+    /// # Arguments
+    ///
+    /// * `worksheets`: The worksheets of the spreadsheet.
+    /// * `meta`: The faculty name and optionally a defined speciality.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the parsed `ExamSession` if successful, or an error if parsing fails.
+    pub fn from_worksheets(worksheets: &[(String, Range<Data>)], meta: FacultyMeta) -> ScheduleResult<Self> {
+        let mut exams = vec![];
+        let mut skipped_rows = vec![];
+
+        for (sheet, range) in worksheets.iter().filter(|(_, range)| Self::is_session_sheet(range)) {
+            Self::parse_sheet(sheet, range, meta.speciality, &mut exams, &mut skipped_rows)?;
+        }
+
+        exams.sort_by(|a, b| {
+            (a.date, a.time, &a.discipline, &a.group).cmp(&(b.date, b.time, &b.discipline, &b.group))
+        });
+
+        Ok(ExamSession { faculty: meta.name, exams, skipped_rows })
+    }
+
+    /// Parses exam rows of a worksheet.
+    fn parse_sheet(
+        sheet: &str,
+        range: &Range<Data>,
+        defined_speciality: Option<SpecialityName>,
+        exams: &mut Vec<Exam>,
+        skipped_rows: &mut Vec<SkippedRow>,
+    ) -> ScheduleResult<()> {
+        // Exams before the first date cell have no date, so they are skipped
+        let mut reserved_date: Option<Date> = None;
+        let mut reserved_time = LessonTime::default();
+
+        let first_row = header_row(range).map_or(0, |header| header + 1);
+        let start_row = range.start().map_or(0, |(row, _)| row);
+
+        for (index, row) in range.rows().enumerate().skip(first_row) {
+            // Rows of narrow worksheets may lack the last columns
+            let cell = |column: usize| row.get(column).unwrap_or(&Data::Empty);
+
+            // Get exam date (may be a text or a date cell)
+            let date = match cell(0) {
+                Data::String(s) if s.trim() == Self::HEADER => continue,
+                Data::String(s) if !s.trim().is_empty() => {
+                    reserved_date = Some(Date::from_str(s)?);
+                    reserved_date
+                },
+                Data::DateTime(serial) => {
+                    reserved_date = Some(Date::from_excel_serial(serial.as_f64())?);
+                    reserved_date
+                },
+                Data::Float(serial) => {
+                    reserved_date = Some(Date::from_excel_serial(*serial)?);
+                    reserved_date
+                },
+                _ => reserved_date,
+            };
+
+            // Get exam time (a range, a start time or a time cell)
+            let time = match cell(1) {
+                Data::String(s) if !s.trim().is_empty() => {
+                    reserved_time = LessonTime::from_str(s)?;
+                    reserved_time
+                },
+                Data::DateTime(value) => {
+                    reserved_time = LessonTime::starting_at(Time::from_excel_fraction(value.as_f64())?);
+                    reserved_time
+                },
+                Data::Float(value) => {
+                    reserved_time = LessonTime::starting_at(Time::from_excel_fraction(*value)?);
+                    reserved_time
+                },
+                _ => reserved_time,
+            };
+
+            let discipline = cell(2).to_string().replace("  ", " ").replace('\n', "");
+            if discipline.trim().is_empty() {
+                skipped_rows.push(SkippedRow {
+                    sheet: sheet.to_owned(),
+                    row: start_row + index as u32 + 1,
+                    reason: SkipReason::of_lesson_row(&row[..row.len().min(5)]).unwrap_or(SkipReason::MissingDiscipline),
+                });
+                continue;
+            }
+
+            let Some(date) = date else {
+                skipped_rows.push(SkippedRow {
+                    sheet: sheet.to_owned(),
+                    row: start_row + index as u32 + 1,
+                    reason: SkipReason::MissingDate,
+                });
+                continue;
+            };
+
+            // Get groups passing the exam (all groups if empty)
+            let group = match cell(3) {
+                Data::String(s) => LessonType::from_str(s)?,
+                Data::Int(number) => LessonType::seminar(*number as u8),
+                Data::Float(number) => LessonType::seminar(*number as u8),
+                Data::Empty => LessonType::new(LessonKind::Exam, vec![], None),
+                other => Err(ScheduleError::InvalidLessonType(other.to_string()))?,
+            };
+
+            let auditorium = match cell(4) {
+                Data::String(s) => Auditorium::from_str(s)?,
+                other => Err(ScheduleError::InvalidAuditorium(other.to_string()))?
+            };
+
+            let specialities = match defined_speciality {
+                Some(speciality) => vec![speciality],
+                None => SpecialityName::from_discipline(&discipline),
+            };
+
+            exams.push(Exam { date, time, discipline, group, auditorium, specialities });
+        }
+
+        Ok(())
+    }
+
+    /// Checks the session for conflicts: several exams of different disciplines
+    /// for the same speciality on the same day, or several exams in the same
+    /// auditorium at overlapping times.
+    ///
+    /// # Returns
+    ///
+    /// A list of the found conflicts.
+    pub fn conflicts(&self) -> Vec<ExamConflict> {
+        let mut conflicts = vec![];
+
+        for (index, first) in self.exams.iter().enumerate() {
+            for second in &self.exams[index + 1..] {
+                if first.date != second.date || first.discipline == second.discipline {
+                    continue;
+                }
+
+                let common_specialities: Vec<SpecialityName> = first.specialities
+                    .iter()
+                    .filter(|s| **s != SpecialityName::General && second.specialities.contains(s))
+                    .copied()
+                    .collect();
+
+                for speciality in common_specialities {
+                    conflicts.push(ExamConflict::SameDay {
+                        speciality,
+                        date: first.date,
+                        disciplines: (first.discipline.clone(), second.discipline.clone()),
+                    });
+                }
+
+                if first.time.overlaps(&second.time) && first.auditorium == second.auditorium && first.auditorium.is_in_person() {
+                    conflicts.push(ExamConflict::SameAuditorium {
+                        auditorium: first.auditorium.clone(),
+                        date: first.date,
+                        disciplines: (first.discipline.clone(), second.discipline.clone()),
+                    });
+                }
+            }
+        }
+
+        conflicts
+    }
+}
+
+/// Represents a conflict found in an exam session.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExamConflict {
+    /// Several exams for the same speciality on the same day.
+    SameDay {
+        /// The speciality passing both exams.
+        speciality: SpecialityName,
+        /// The date of both exams.
+        date: Date,
+        /// The conflicting disciplines.
+        disciplines: (String, String),
+    },
+    /// Several exams in the same auditorium at overlapping times.
+    SameAuditorium {
+        /// The auditorium of both exams.
+        auditorium: Auditorium,
+        /// The date of both exams.
+        date: Date,
+        /// The conflicting disciplines.
+        disciplines: (String, String),
+    },
+}
+
+impl ExamConflict {
+    /// Returns the names of both conflicting disciplines.
+    pub fn disciplines(&self) -> (&str, &str) {
+        match self {
+            ExamConflict::SameDay { disciplines: (first, second), .. }
+                | ExamConflict::SameAuditorium { disciplines: (first, second), .. } => (first, second),
+        }
+    }
+}
+
+impl Display for ExamConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExamConflict::SameDay { speciality, date, disciplines: (first, second) } => write!(
+                f, "Two exams for `{speciality}` on {date}: `{first}` and `{second}`"
+            ),
+            ExamConflict::SameAuditorium { auditorium, date, disciplines: (first, second) } => write!(
+                f, "Two exams in `{auditorium}` at the same time on {date}: `{first}` and `{second}`"
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spreadsheet::read_csv;
+
+    /// Parses the exam rows of a worksheet of the Економіка speciality.
+    fn parse(range: &Range<Data>) -> ScheduleResult<Vec<Exam>> {
+        let mut exams = vec![];
+        ExamSession::parse_sheet("Аркуш1", range, Some(SpecialityName::Economics), &mut exams, &mut vec![])?;
+
+        Ok(exams)
+    }
+
+    #[test]
+    fn parses_dates() {
+        let expected = Date::new(2024, 1, 15).unwrap();
+
+        assert_eq!(Date::from_str("15.01.2024").unwrap(), expected);
+        assert_eq!(Date::from_str("15/01/24").unwrap(), expected);
+        assert_eq!(Date::from_str("2024-01-15").unwrap(), expected);
+        assert_eq!(Date::from_excel_serial(45306.0).unwrap(), expected);
+        assert!(Date::from_str("29.02.2024").is_ok());
+        assert!(matches!(Date::from_str("31.02.2024"), Err(ScheduleError::InvalidValue { .. })));
+        assert!(matches!(Date::from_str("15 січня"), Err(ScheduleError::InvalidDate(_))));
+    }
+
+    #[test]
+    fn parses_exam_times() {
+        let range = read_csv(
+            "Дата;Час;Дисципліна;Група;Аудиторія\n\
+             15.01.2024;10:00;Мікроекономіка;;1-225\n\
+             16.01.2024;08:30-09:50;Макроекономіка;2;3-205\n",
+        ).unwrap();
+        let exams = parse(&range).unwrap();
+
+        assert_eq!(exams[0].time, LessonTime::starting_at(Time::new(10, 0).unwrap()));
+        assert_eq!(exams[0].time.to_string(), "10:00");
+        assert_eq!(exams[0].group.kind, LessonKind::Exam);
+        assert_eq!(exams[1].time.to_string(), "08:30-09:50");
+    }
+
+    #[test]
+    fn parses_time_cells() {
+        let mut range = read_csv("Дата;Час;Дисципліна;Група;Аудиторія\n15.01.2024;;Мікроекономіка;;1-225\n").unwrap();
+        range.set_value((1, 1), Data::Float(10.5 / 24.0));
+
+        assert_eq!(parse(&range).unwrap()[0].time.to_string(), "10:30");
+    }
+
+    #[test]
+    fn short_rows_do_not_panic() {
+        let mut range = Range::new((0, 0), (2, 2));
+        range.set_value((0, 0), Data::String("Дата".to_owned()));
+        range.set_value((1, 0), Data::String("15.01.2024".to_owned()));
+        range.set_value((1, 1), Data::String("10:00".to_owned()));
+        range.set_value((2, 2), Data::String("Мікроекономіка".to_owned()));
+
+        assert!(matches!(parse(&range), Err(ScheduleError::InvalidAuditorium(_))));
+    }
+
+    #[test]
+    fn skips_exams_before_the_first_date() {
+        let range = read_csv(
+            "Дата;Час;Дисципліна;Група;Аудиторія\n\
+             ;10:00;Мікроекономіка;;1-225\n\
+             16.01.2024;08:30-09:50;Макроекономіка;2;3-205\n",
+        ).unwrap();
+        let mut exams = vec![];
+        let mut skipped_rows = vec![];
+        ExamSession::parse_sheet("Аркуш1", &range, None, &mut exams, &mut skipped_rows).unwrap();
+
+        assert_eq!(exams.len(), 1);
+        assert_eq!(exams[0].discipline, "Макроекономіка");
+        assert_eq!(skipped_rows[0].row, 2);
+        assert_eq!(skipped_rows[0].reason, SkipReason::MissingDate);
+        assert!(skipped_rows[0].reason.is_suspicious());
+    }
+
+    #[test]
+    fn finds_conflicts_of_exams_with_start_times() {
+        let range = read_csv(
+            "Дата;Час;Дисципліна;Група;Аудиторія\n\
+             15.01.2024;10:00;Мікроекономіка;;1-225\n\
+             15.01.2024;10:00;Статистика;;1-225\n",
+        ).unwrap();
+        let session = ExamSession { faculty: String::new(), exams: parse(&range).unwrap(), skipped_rows: vec![] };
+
+        // The same speciality on the same day and the same auditorium at the same time
+        assert_eq!(session.conflicts().len(), 2);
+    }
+}
@@ -3,13 +3,17 @@ use std::str::FromStr;
 use serde::{Serialize, Deserialize};
 use validator::{Validate, ValidationErrors};
 
+use crate::building::Building;
 use crate::macros::impl_serde_display_fromstr;
-use crate::error::ScheduleError;
+use crate::error::{ScheduleError, ScheduleResult};
 
 /// Represents a university group, including its name, 
 /// lesson time, studying weeks, auditorium and day of the week.
-#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
 pub struct Group {
+    /// The identifier of the lesson occurrence, a hash of its faculty, discipline, name, day, time, weeks and auditorium.
+    #[serde(rename = "Ідентифікатор", default)]
+    pub id: String,
     /// The name of the group / actually type of the lesson being taught.
     #[serde(rename = "Назва")]
     pub name: LessonType,
@@ -32,23 +36,265 @@ impl_serde_display_fromstr!(LessonType, Time, LessonTime, Weeks, Auditorium, Day
 /// Represents the number of a discipline group.
 pub type GroupNumber = u8;
 
-/// Represents the name of the group / type of a university lesson, 
-/// which can be a lection or classes with a group number.
-#[derive(Default, Clone, Debug)]
-pub enum LessonType {
-    /// A lection lesson type (intended for each group).
+/// Represents the kind of a university lesson.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LessonKind {
+    /// A lection (intended for each group).
     #[default]
     Lection,
-    /// A classes lesson type with a group number specified.
-    Classes(GroupNumber),
+    /// A seminar, also written as a bare group number.
+    Seminar,
+    /// A practical lesson (`практ.`).
+    Practical,
+    /// A laboratory lesson (`лаб.`).
+    Lab,
+    /// A consultation before an exam (`консультація`).
+    Consultation,
+    /// An exam (`екзамен`, `іспит`).
+    Exam,
+    /// A test or credit (`залік`, `тест`, `контрольна`).
+    Test,
 }
 
-impl Display for LessonType {
+impl LessonKind {
+    /// Keywords (in lowercase) identifying lesson kinds in spreadsheet labels.
+    const KEYWORDS: [(&'static str, LessonKind); 11] = [
+        ("лекц", LessonKind::Lection),
+        ("практ", LessonKind::Practical),
+        ("лаб", LessonKind::Lab),
+        ("консульт", LessonKind::Consultation),
+        ("екзам", LessonKind::Exam),
+        ("іспит", LessonKind::Exam),
+        ("залік", LessonKind::Test),
+        ("тест", LessonKind::Test),
+        ("контрольн", LessonKind::Test),
+        ("семінар", LessonKind::Seminar),
+        ("сем.", LessonKind::Seminar),
+    ];
+
+    /// Finds the lesson kind mentioned in a label, if any.
+    pub fn from_label(label: &str) -> Option<Self> {
+        let label = label.to_lowercase();
+
+        Self::KEYWORDS
+            .iter()
+            .find(|(keyword, _)| label.contains(keyword))
+            .map(|(_, kind)| *kind)
+    }
+}
+
+impl Display for LessonKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            LessonType::Lection => write!(f, "Лекція"),
-            LessonType::Classes(number) => write!(f, "{number}")
+        use LessonKind::*;
+
+        let stringed = match self {
+            Lection => "Лекція",
+            Seminar => "Семінар",
+            Practical => "Практичне",
+            Lab => "Лабораторна",
+            Consultation => "Консультація",
+            Exam => "Екзамен",
+            Test => "Залік",
+        };
+
+        write!(f, "{stringed}")
+    }
+}
+
+/// Represents the name of the group / type of a university lesson: its kind,
+/// the numbers of the groups attending it and the original spreadsheet label.
+///
+/// Examples of labels: `лекція`, `2`, `практ. 2`, `лаб. 1`, `2 (англ.)`,
+/// `1-2`, `1,3`, `2а`, `консультація`. Stream numbers (e.g. `Лекція 1п`) are not group numbers,
+/// and only the letters `а`, `б`, `в`, `г` (or `a`, `b`) after a number are sub-groups.
+#[derive(Clone, Debug)]
+pub struct LessonType {
+    /// The kind of the lesson.
+    pub kind: LessonKind,
+    /// Numbers of the groups attending the lesson, in ascending order.
+    /// Empty if the lesson is intended for each group (e.g. a lection).
+    pub groups: Vec<GroupNumber>,
+    /// The sub-group letter (e.g. `а` in `2а`).
+    pub subgroup: Option<char>,
+    /// The original label, as written in the spreadsheet.
+    label: String,
+}
+
+impl LessonType {
+    /// Sub-group letters written right after a group number (e.g. `а` in `2а`).
+    const SUBGROUPS: [char; 6] = ['а', 'б', 'в', 'г', 'a', 'b'];
+    /// Lowercase markers written right after a stream number (e.g. `п` in `Лекція 1п`).
+    const STREAMS: [&'static str; 3] = ["п", "пот", "потік"];
+
+    /// Creates a new `LessonType` with a canonical label.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind`: The kind of the lesson.
+    /// * `groups`: Numbers of the groups attending the lesson.
+    /// * `subgroup`: The sub-group letter.
+    ///
+    /// # Returns
+    ///
+    /// The created `LessonType`.
+    pub fn new(kind: LessonKind, mut groups: Vec<GroupNumber>, subgroup: Option<char>) -> Self {
+        groups.sort_unstable();
+        groups.dedup();
+
+        let mut label = Self::format_groups(&groups);
+        if let Some(letter) = subgroup {
+            label.push(letter);
+        }
+
+        let label = match kind {
+            LessonKind::Seminar if !label.is_empty() => label,
+            _ if label.is_empty() => kind.to_string(),
+            _ => format!("{kind} {label}"),
+        };
+
+        LessonType { kind, groups, subgroup, label }
+    }
+
+    /// Creates a new lection `LessonType`.
+    pub fn lection() -> Self {
+        Self::new(LessonKind::Lection, vec![], None)
+    }
+
+    /// Creates a new seminar `LessonType` for a group written as a bare number.
+    pub fn seminar(number: GroupNumber) -> Self {
+        Self::new(LessonKind::Seminar, vec![number], None)
+    }
+
+    /// Checks whether the lesson is intended for each group (e.g. a lection).
+    pub fn is_for_each_group(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    /// Checks whether the given group attends the lesson.
+    pub fn includes(&self, group: GroupNumber) -> bool {
+        self.groups.is_empty() || self.groups.contains(&group)
+    }
+
+    /// Returns the original label of the lesson type.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Formats group numbers, joining consecutive ones into ranges (e.g. `1-3,5`).
+    fn format_groups(groups: &[GroupNumber]) -> String {
+        let mut parts: Vec<String> = vec![];
+        let mut index = 0;
+
+        while index < groups.len() {
+            let first = groups[index];
+            let mut last = first;
+
+            while index + 1 < groups.len() && groups[index + 1] == last + 1 {
+                index += 1;
+                last = groups[index];
+            }
+
+            if first == last {
+                parts.push(first.to_string());
+            } else {
+                parts.push(format!("{first}-{last}"));
+            }
+
+            index += 1;
         }
+
+        parts.join(",")
+    }
+
+    /// Parses group numbers and a sub-group letter from a label,
+    /// ignoring the text in parentheses (e.g. `(англ.)`).
+    fn parse_groups(label: &str) -> ScheduleResult<(Vec<GroupNumber>, Option<char>)> {
+        let mut groups = vec![];
+        let mut subgroup = None;
+
+        let mut depth = 0;
+        let mut chars = label.chars().peekable();
+        let mut range_start: Option<GroupNumber> = None;
+
+        while let Some(c) = chars.next() {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                _ if depth > 0 => {},
+                '-' | '–' if !groups.is_empty() => range_start = groups.last().copied(),
+                _ if c.is_ascii_digit() => {
+                    let mut number = c.to_string();
+                    while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+                        number.push(digit);
+                    }
+
+                    let number = number.parse::<GroupNumber>()
+                        .map_err(|_| ScheduleError::InvalidLessonType(label.to_owned()))?;
+
+                    // Letters right after the number: a sub-group, a stream marker or an abbreviation
+                    let mut suffix = String::new();
+                    while let Some(letter) = chars.next_if(|c| c.is_alphabetic()) {
+                        suffix.extend(letter.to_lowercase());
+                    }
+
+                    // A stream number (e.g. `1п` in `Лекція 1п`) is not a group number
+                    if Self::STREAMS.contains(&suffix.as_str()) {
+                        range_start = None;
+                        continue;
+                    }
+
+                    match range_start.take() {
+                        Some(first) if first < number => groups.extend(first + 1..=number),
+                        Some(_) => Err(ScheduleError::InvalidLessonType(label.to_owned()))?,
+                        None => groups.push(number),
+                    }
+
+                    let mut letters = suffix.chars();
+                    if let (Some(letter), None) = (letters.next(), letters.next()) {
+                        if Self::SUBGROUPS.contains(&letter) {
+                            subgroup = Some(letter);
+                        }
+                    }
+                },
+                _ => {},
+            }
+        }
+
+        Ok((groups, subgroup))
+    }
+}
+
+impl Default for LessonType {
+    fn default() -> Self {
+        LessonType::lection()
+    }
+}
+
+impl PartialEq for LessonType {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for LessonType {}
+
+impl PartialOrd for LessonType {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Lesson types are ordered by group numbers (lections first), while the original
+/// label does not take part in comparison.
+impl Ord for LessonType {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (&self.groups, self.subgroup, self.kind).cmp(&(&other.groups, other.subgroup, other.kind))
+    }
+}
+
+impl Display for LessonType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label)
     }
 }
 
@@ -56,16 +302,18 @@ impl FromStr for LessonType {
     type Err = ScheduleError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.contains("лекція") || s.contains("Лекція") {
-            Ok(LessonType::Lection)
-        } else {
-            let number: String = s.chars().filter(|c| c.is_ascii_digit()).collect();
+        let label = s.split_whitespace().collect::<Vec<&str>>().join(" ");
+        let (mut groups, subgroup) = Self::parse_groups(&label)?;
+        groups.sort_unstable();
+        groups.dedup();
 
-            Ok(
-                LessonType::Classes(number.parse::<u8>()
-                    .map_err(|_| ScheduleError::InvalidLessonType(s.to_owned()))?)
-            )
-        }
+        let kind = match LessonKind::from_label(&label) {
+            Some(kind) => kind,
+            None if !groups.is_empty() => LessonKind::Seminar,
+            None => Err(ScheduleError::InvalidLessonType(s.to_owned()))?,
+        };
+
+        Ok(LessonType { kind, groups, subgroup, label })
     }
 }
 
@@ -73,7 +321,7 @@ impl FromStr for LessonType {
 ///
 /// The `Time` struct is used to represent a specific time of day and can be validated
 /// to ensure that the hours and minutes are within the valid range.
-#[derive(Validate, Default, Clone, Copy, Debug)]
+#[derive(Validate, Default, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Time {
     /// The hours component of the time
     #[validate(range(min = 0, max = 23))]
@@ -101,6 +349,37 @@ impl Time {
 
         Ok(time)
     }
+
+    /// Returns the hours component of the time.
+    pub fn hours(&self) -> u8 {
+        self.hours
+    }
+
+    /// Returns the minutes component of the time.
+    pub fn minutes(&self) -> u8 {
+        self.minutes
+    }
+
+    /// Returns the number of minutes passed since midnight.
+    pub fn minutes_of_day(&self) -> u16 {
+        u16::from(self.hours) * 60 + u16::from(self.minutes)
+    }
+
+    /// Creates a new `Time` from an Excel time value, a fraction of the day
+    /// (e.g. `0.4166` of `10:00`). The integer part of date-time values is ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `value`: The Excel time or date-time value.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the time rounded to minutes, or validation errors of negative values.
+    pub fn from_excel_fraction(value: f64) -> Result<Self, ValidationErrors> {
+        let minutes = (value.fract() * 1440.0).round() as i64;
+
+        Time::new((minutes / 60 % 24) as u8, (minutes % 60) as u8)
+    }
 }
 
 impl Display for Time {
@@ -133,12 +412,12 @@ impl FromStr for Time {
             let hours = h.parse::<u8>().map_err(|_| ScheduleError::InvalidTimeFormat(s.to_owned()))?;
             let minutes = m.parse::<u8>().map_err(|_| ScheduleError::InvalidTimeFormat(s.to_owned()))?;
 
-            Ok(Time::new(hours, minutes)?)
+            Time::new(hours, minutes).map_err(|errors| ScheduleError::invalid_value(s, errors))
         } else if let Some((h, m)) = s.split_once('.') {
             let hours = h.parse::<u8>().map_err(|_| ScheduleError::InvalidTimeFormat(s.to_owned()))?;
             let minutes = m.parse::<u8>().map_err(|_| ScheduleError::InvalidTimeFormat(s.to_owned()))?;
 
-            Ok(Time::new(hours, minutes)?)
+            Time::new(hours, minutes).map_err(|errors| ScheduleError::invalid_value(s, errors))
         } else {
             Err(ScheduleError::InvalidTimeFormat(s.to_owned()))
         }
@@ -146,7 +425,10 @@ impl FromStr for Time {
 }
 
 /// Represents a time range for a lesson, including start and end time.
-#[derive(Default, Clone, Copy, Debug)]
+///
+/// Exams are often written with the start time only (e.g. `10:00`),
+/// which is represented by equal start and end times.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct LessonTime {
     /// The starting time of the lesson.
     pub from: Time,
@@ -154,9 +436,42 @@ pub struct LessonTime {
     pub to: Time,
 }
 
+impl LessonTime {
+    /// Creates a new `LessonTime` with the start time only.
+    pub fn starting_at(from: Time) -> Self {
+        LessonTime { from, to: from }
+    }
+
+    /// Checks whether the end time is known.
+    pub fn has_end(&self) -> bool {
+        self.from < self.to
+    }
+
+    /// Checks whether two time ranges overlap. Ranges with the start time only
+    /// overlap the ranges containing their start and the ones starting at the same time.
+    pub fn overlaps(&self, other: &LessonTime) -> bool {
+        self.from == other.from || (self.from < other.to && other.from < self.to)
+    }
+
+    /// Returns the duration of the lesson in minutes.
+    pub fn duration(&self) -> u16 {
+        self.to.minutes_of_day().saturating_sub(self.from.minutes_of_day())
+    }
+
+    /// Returns the break in minutes between the end of this lesson and the start of the next one,
+    /// or `None` if the lessons overlap.
+    pub fn break_until(&self, next: &LessonTime) -> Option<u16> {
+        next.from.minutes_of_day().checked_sub(self.to.minutes_of_day())
+    }
+}
+
 impl Display for LessonTime {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}-{}", self.from, self.to)
+        if self.from == self.to {
+            write!(f, "{}", self.from)
+        } else {
+            write!(f, "{}-{}", self.from, self.to)
+        }
     }
 }
 
@@ -166,9 +481,11 @@ impl FromStr for LessonTime {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if let Some((from, to)) = s.split_once('-') {
             Ok(LessonTime {
-                from: Time::from_str(from)?, 
-                to: Time::from_str(to)?
+                from: Time::from_str(from.trim())?,
+                to: Time::from_str(to.trim())?
             })
+        } else if let Ok(from) = Time::from_str(s.trim()) {
+            Ok(LessonTime::starting_at(from))
         } else {
             Err(ScheduleError::InvalidLessonTime(s.to_owned()))
         }
@@ -189,30 +506,42 @@ pub enum Weeks {
     },
     /// A combination of multiple week specifications. Example `1,3-5,7,8,10-12`.
     Combined(Vec<Weeks>),
+    /// Every `step`-th week from the first one until the end of the semester.
+    /// Examples `непарні`, `парні`, `щотижня`, `кожен другий тиждень з 2`.
+    Every {
+        /// The first week.
+        first: u8,
+        /// The number of weeks between lessons.
+        step: u8,
+        /// The last week of the semester.
+        last: u8,
+    },
 }
 
-impl Display for Weeks {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Weeks::Single(day) => write!(f, "{day}"),
-            Weeks::Range { first, last } => write!(f, "{first}-{last}"),
-            Weeks::Combined(weeks) => {
-                let mut display = String::new();
+impl Weeks {
+    /// The default number of studying weeks of the semester.
+    pub const DEFAULT_SEMESTER_WEEKS: u8 = 15;
 
-                for week in weeks {
-                    display.push_str(format!("{week},").as_str())
-                }
+    /// Ordinal numerals of steps in `кожен другий тиждень з 2`-like patterns.
+    /// Other steps are written with numbers, e.g. `кожен 5-й тиждень з 1`.
+    const STEPS: [(&'static str, u8); 3] = [("другий", 2), ("третій", 3), ("четвертий", 4)];
 
-                write!(f, "{}", display.trim_end_matches(','))
-            }
+    /// Parses the weeks of a lesson: a list of weeks (e.g. `1,3-8,10`) or a pattern written in words
+    /// (e.g. `непарні`), which lasts until the end of the semester.
+    ///
+    /// # Arguments
+    ///
+    /// * `s`: The weeks as written in a spreadsheet.
+    /// * `semester_weeks`: The number of studying weeks of the semester.
+    ///
+    /// # Returns
+    ///
+    /// A `ScheduleResult` containing the parsed weeks, or an error if the format is invalid.
+    pub fn parse(s: &str, semester_weeks: u8) -> ScheduleResult<Self> {
+        if let Some(weeks) = Weeks::from_pattern(s, semester_weeks) {
+            return Ok(weeks);
         }
-    }
-}
-
-impl FromStr for Weeks {
-    type Err = ScheduleError;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
         let s = s.replace(&[' ', '\n'][..], "");
 
         let elements: Vec<&str> = s.split(',').collect();
@@ -226,21 +555,177 @@ impl FromStr for Weeks {
                 Ok(Weeks::Range { first, last })
             // Single
             } else if let Ok(day) = s.parse::<u8>() {
-                return Ok(Weeks::Single(day));
+                Ok(Weeks::Single(day))
             // Invalid
             } else {
-                return Err(ScheduleError::InvalidWeeksFormat(s.to_owned()));
+                Err(ScheduleError::InvalidWeeksFormat(s.to_owned()))
             }
         // Combined
         } else {
             let mut weeks = vec![];
             for e in elements {
-                weeks.push(Weeks::from_str(e)?);
+                weeks.push(Weeks::parse(e, semester_weeks)?);
             }
     
             Ok(Weeks::Combined(weeks))
         }
     }
+
+    /// Returns every week number covered by this specification, in ascending order.
+    pub fn numbers(&self) -> Vec<u8> {
+        let mut numbers = match self {
+            Weeks::Single(week) => vec![*week],
+            Weeks::Range { first, last } => (*first..=*last).collect(),
+            Weeks::Combined(weeks) => weeks.iter().flat_map(Weeks::numbers).collect(),
+            Weeks::Every { first, step, last } => (*first..=*last).step_by((*step).max(1) as usize).collect(),
+        };
+
+        numbers.sort_unstable();
+        numbers.dedup();
+        numbers
+    }
+
+    /// Checks whether the lesson takes place during the given week.
+    pub fn contains(&self, week: u8) -> bool {
+        match self {
+            Weeks::Single(single) => *single == week,
+            Weeks::Range { first, last } => (*first..=*last).contains(&week),
+            Weeks::Combined(weeks) => weeks.iter().any(|w| w.contains(week)),
+            Weeks::Every { first, step, last } => {
+                (*first..=*last).contains(&week) && (week - first).is_multiple_of((*step).max(1))
+            },
+        }
+    }
+
+    /// Checks whether two lessons share at least one week.
+    pub fn overlaps(&self, other: &Weeks) -> bool {
+        self.numbers().into_iter().any(|week| other.contains(week))
+    }
+
+    /// Creates the shortest specification of the given week numbers, joining consecutive weeks into ranges.
+    ///
+    /// # Arguments
+    ///
+    /// * `numbers`: Week numbers in any order, possibly repeated.
+    ///
+    /// # Returns
+    ///
+    /// The specification (e.g. `1-13` of weeks 1 to 13, `1-5,7` of weeks 1 to 5 and 7).
+    pub fn from_numbers(numbers: &[u8]) -> Self {
+        let mut numbers = numbers.to_vec();
+        numbers.sort_unstable();
+        numbers.dedup();
+
+        let mut parts = vec![];
+        let mut index = 0;
+
+        while index < numbers.len() {
+            let first = numbers[index];
+            while index + 1 < numbers.len() && numbers[index + 1] == numbers[index] + 1 {
+                index += 1;
+            }
+
+            let last = numbers[index];
+            parts.push(if first == last { Weeks::Single(first) } else { Weeks::Range { first, last } });
+            index += 1;
+        }
+
+        match parts.len() {
+            0 => Weeks::default(),
+            1 => parts.remove(0),
+            _ => Weeks::Combined(parts),
+        }
+    }
+
+    /// Parses a week pattern written in words, lasting until the last week of the semester
+    /// or the week after `до` (e.g. `непарні до 14`).
+    fn from_pattern(s: &str, semester_weeks: u8) -> Option<Self> {
+        let s = s.split_whitespace().collect::<Vec<&str>>().join(" ").to_lowercase();
+
+        let (s, last) = match s.rsplit_once(" до ").map(|(pattern, last)| (pattern, last.parse())) {
+            Some((pattern, Ok(last))) => (pattern.to_owned(), last),
+            _ => (s, semester_weeks),
+        };
+
+        let (first, step) = match s.trim_end_matches(" тижні") {
+            "непарні" => (1, 2),
+            "парні" => (2, 2),
+            "щотижня" | "кожен тиждень" => (1, 1),
+            _ => {
+                let rest = s.strip_prefix("кожен ")?;
+                let (step, rest) = Self::STEPS
+                    .iter()
+                    .find_map(|(word, step)| rest.strip_prefix(word).map(|rest| (*step, rest)))
+                    .or_else(|| {
+                        let (step, rest) = rest.split_once("-й")?;
+                        Some((step.parse().ok().filter(|step| *step > 0)?, rest))
+                    })?;
+                let first = rest.trim().strip_prefix("тиждень")?.trim().strip_prefix('з')?.trim().parse().ok()?;
+
+                (first, step)
+            },
+        };
+
+        Some(Weeks::Every { first, step, last })
+    }
+
+    /// Returns the pattern written in words (e.g. `непарні`), with the last week
+    /// if it is not the end of the default semester (e.g. `непарні до 14`).
+    fn pattern_name(first: u8, step: u8, last: u8) -> String {
+        let name = match (first, step) {
+            (1, 1) => "щотижня".to_owned(),
+            (1, 2) => "непарні".to_owned(),
+            (2, 2) => "парні".to_owned(),
+            _ => match Self::STEPS.iter().find(|(_, s)| *s == step) {
+                Some((word, _)) => format!("кожен {word} тиждень з {first}"),
+                None => format!("кожен {step}-й тиждень з {first}"),
+            },
+        };
+
+        if last == Self::DEFAULT_SEMESTER_WEEKS {
+            name
+        } else {
+            format!("{name} до {last}")
+        }
+    }
+}
+
+impl Display for Weeks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Weeks::Single(day) => write!(f, "{day}"),
+            Weeks::Range { first, last } => write!(f, "{first}-{last}"),
+            Weeks::Combined(weeks) => {
+                let mut display = String::new();
+
+                for week in weeks {
+                    display.push_str(format!("{week},").as_str())
+                }
+
+                write!(f, "{}", display.trim_end_matches(','))
+            }
+            Weeks::Every { first, step, last } => {
+                let pattern = Self::pattern_name(*first, *step, *last);
+                let numbers = self.numbers();
+                let explicit = Weeks::from_numbers(&numbers).to_string();
+
+                if numbers.is_empty() || pattern.chars().count() < explicit.chars().count() {
+                    write!(f, "{pattern}")
+                } else {
+                    write!(f, "{explicit}")
+                }
+            }
+        }
+    }
+}
+
+impl FromStr for Weeks {
+    type Err = ScheduleError;
+
+    /// Parses the weeks with patterns lasting until the end of the default semester (see `Weeks::parse`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Weeks::parse(s, Self::DEFAULT_SEMESTER_WEEKS)
+    }
 }
 
 impl Default for Weeks {
@@ -250,24 +735,112 @@ impl Default for Weeks {
 }
 
 /// Represents an auditorium for university lessons, which can be a distance learning auditorium (online),
-/// an art center (for lections), or a pavilion with a specific room number.
-#[derive(Default, Clone, Debug)]
+/// a named building (e.g. the Culture Art Center for lections), a pavilion with a specific room number,
+/// a hybrid lesson, or an auditorium to be announced.
+#[derive(Clone, Debug, PartialEq)]
 pub enum Auditorium {
     /// Auditorium type representing distance learning.
-    #[default]
-    Distance,
-    /// Auditorium type representing the Culture Art Center.
-    ArtCenter,
+    Distance {
+        /// The meeting link (or platform name, e.g. `Zoom`).
+        link: Option<String>,
+    },
+    /// Auditorium type representing a named building without room numbers
+    /// (e.g. the Culture Art Center, the library or the sports hall).
+    Named(&'static Building),
     /// Auditorium type representing a pavilion with a specific number.
     Pavilion(AuditoriumNumber),
+    /// Auditorium type representing a hybrid lesson, held in a room and online at the same time.
+    Hybrid {
+        /// The room of the lesson.
+        room: AuditoriumNumber,
+        /// The meeting link (or platform name, e.g. `Zoom`).
+        link: Option<String>,
+    },
+    /// Auditorium type representing a room which is not known yet.
+    ToBeAnnounced,
+}
+
+impl Auditorium {
+    /// Lowercase markers of distance learning.
+    const DISTANCE: [&'static str; 7] = ["дистанційно", "д", "online", "онлайн", "zoom", "teams", "meet"];
+    /// Lowercase markers of auditoriums to be announced.
+    const TO_BE_ANNOUNCED: [&'static str; 6] = ["тба", "tba", "уточнюється", "буде повідомлено", "-", "?"];
+    /// Lowercase prefixes of auditorium numbers.
+    const PREFIXES: [&'static str; 3] = ["аудиторія", "ауд.", "ауд"];
+
+    /// Returns the room of the lesson, if it is held in a numbered room.
+    pub fn room(&self) -> Option<&AuditoriumNumber> {
+        match self {
+            Auditorium::Pavilion(room) | Auditorium::Hybrid { room, .. } => Some(room),
+            _ => None,
+        }
+    }
+
+    /// Returns the building of the lesson, if it is held in person.
+    pub fn building(&self) -> Option<&'static Building> {
+        match self {
+            Auditorium::Named(building) => Some(building),
+            _ => self.room().and_then(AuditoriumNumber::building),
+        }
+    }
+
+    /// Returns the meeting link of a distance or hybrid lesson.
+    pub fn link(&self) -> Option<&str> {
+        match self {
+            Auditorium::Distance { link } | Auditorium::Hybrid { link, .. } => link.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Checks whether the lesson takes place in a physical auditorium.
+    pub fn is_in_person(&self) -> bool {
+        self.building().is_some()
+    }
+
+    /// Checks whether the lesson can be attended online.
+    pub fn is_online(&self) -> bool {
+        matches!(self, Auditorium::Distance { .. } | Auditorium::Hybrid { .. })
+    }
+
+    /// Parses a single auditorium part (without links and `+` separators).
+    fn parse_part(part: &str) -> ScheduleResult<Auditorium> {
+        let mut part = part.trim().to_lowercase();
+
+        for prefix in Self::PREFIXES {
+            if let Some(stripped) = part.strip_prefix(prefix) {
+                part = stripped.trim().to_owned();
+                break;
+            }
+        }
+
+        if Self::DISTANCE.contains(&part.as_str()) {
+            Ok(Auditorium::Distance { link: None })
+        } else if part.is_empty() || Self::TO_BE_ANNOUNCED.contains(&part.as_str()) {
+            Ok(Auditorium::ToBeAnnounced)
+        } else if let Some(building) = Building::find(&part).filter(|b| !b.is_pavilion()) {
+            Ok(Auditorium::Named(building))
+        } else {
+            Ok(Auditorium::Pavilion(AuditoriumNumber::from_str(&part)?))
+        }
+    }
+}
+
+impl Default for Auditorium {
+    fn default() -> Self {
+        Auditorium::Distance { link: None }
+    }
 }
 
 impl Display for Auditorium {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Auditorium::Distance => write!(f, "Дистанційно"),
-            Auditorium::ArtCenter => write!(f, "КМЦ"),
+            Auditorium::Distance { link: None } => write!(f, "Дистанційно"),
+            Auditorium::Distance { link: Some(link) } => write!(f, "Дистанційно ({link})"),
+            Auditorium::Named(building) => write!(f, "{}", building.code),
             Auditorium::Pavilion(number) => write!(f, "{number}"),
+            Auditorium::Hybrid { room, link: None } => write!(f, "{room} + Дистанційно"),
+            Auditorium::Hybrid { room, link: Some(link) } => write!(f, "{room} + Дистанційно ({link})"),
+            Auditorium::ToBeAnnounced => write!(f, "ТБА"),
         }
     }
 }
@@ -275,21 +848,79 @@ impl Display for Auditorium {
 impl FromStr for Auditorium {
     type Err = ScheduleError;
 
+    /// Parses auditoriums like `3-205`, `ауд. 1-225а`, `КМЦ`, `Бібліотека`, `Д`,
+    /// `online (Zoom)`, `Дистанційно https://...`, `6-204 + Д` or `тба`.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "Дистанційно" | "Д" | "д" => Ok(Auditorium::Distance),
-            "КМЦ" | "кмц" => Ok(Auditorium::ArtCenter),
-            _ => Ok(Auditorium::Pavilion(AuditoriumNumber::from_str(s)?))
+        let invalid = || ScheduleError::InvalidAuditorium(s.to_owned());
+
+        // Meeting links and platform names in parentheses
+        let mut link = None;
+        let mut rest = String::new();
+        let mut depth = 0;
+        let mut note = String::new();
+
+        for word in s.split_whitespace() {
+            if word.starts_with("http://") || word.starts_with("https://") {
+                link = Some(word.to_owned());
+            } else {
+                rest.push_str(word);
+                rest.push(' ');
+            }
+        }
+
+        let mut cleaned = String::new();
+        for c in rest.chars() {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                _ if depth > 0 => note.push(c),
+                _ => cleaned.push(c),
+            }
+        }
+
+        let note = note.trim();
+        if link.is_none() && !note.is_empty() {
+            link = Some(note.to_owned());
+        }
+
+        let mut room = None;
+        let mut online = false;
+        let mut named = None;
+        let mut to_be_announced = false;
+
+        // Building aliases may contain separators themselves (e.g. `с/з`)
+        if let Some(building) = Building::find(&cleaned).filter(|b| !b.is_pavilion()) {
+            return Ok(Auditorium::Named(building));
+        }
+
+        for part in cleaned.split(['+', '/']) {
+            match Self::parse_part(part)? {
+                Auditorium::Distance { .. } => online = true,
+                Auditorium::Pavilion(number) if room.is_none() => room = Some(number),
+                Auditorium::Named(building) if named.is_none() => named = Some(building),
+                Auditorium::ToBeAnnounced => to_be_announced = true,
+                _ => Err(invalid())?,
+            }
+        }
+
+        match (room, named, online) {
+            (Some(room), None, true) => Ok(Auditorium::Hybrid { room, link }),
+            (Some(room), None, false) => Ok(Auditorium::Pavilion(room)),
+            (None, Some(building), false) => Ok(Auditorium::Named(building)),
+            (None, None, true) => Ok(Auditorium::Distance { link }),
+            (None, None, false) if to_be_announced => Ok(Auditorium::ToBeAnnounced),
+            _ => Err(invalid()),
         }
     }
 }
 
-/// Represents an auditorium number, including a pavilion number and a room number.
+/// Represents an auditorium number, including a pavilion number, a room number
+/// and an optional room suffix.
 ///
 /// The `AuditoriumNumber` struct is used to represent a specific auditorium number,
 /// and it can be validated to ensure that both the pavilion and room numbers are within
 /// their respective valid ranges.
-#[derive(Validate, Default, Clone, Debug)]
+#[derive(Validate, Default, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct AuditoriumNumber {
     /// The pavilion number.
     #[validate(range(min = 1, max = 9))]
@@ -297,6 +928,8 @@ pub struct AuditoriumNumber {
     /// The room number.
     #[validate(range(min = 1, max = 599))]
     room: u16,
+    /// The room suffix (e.g. `а` in `1-225а`).
+    suffix: Option<char>,
 }
 
 impl AuditoriumNumber {
@@ -312,16 +945,59 @@ impl AuditoriumNumber {
     /// A `Result` containing the created `AuditoriumNumber` if it passes validation,
     /// or validation errors if validation fails.
     pub fn new(pavilion: u8, room: u16) -> Result<Self, ValidationErrors> {
-        let number = AuditoriumNumber { pavilion, room };
+        Self::with_suffix(pavilion, room, None)
+    }
+
+    /// Creates a new `AuditoriumNumber` instance with the specified pavilion and room numbers
+    /// and a room suffix.
+    ///
+    /// # Arguments
+    ///
+    /// * `pavilion`: The pavilion number.
+    /// * `room`: The room number.
+    /// * `suffix`: The room suffix (e.g. `а` in `1-225а`).
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the created `AuditoriumNumber` if it passes validation,
+    /// or validation errors if validation fails.
+    pub fn with_suffix(pavilion: u8, room: u16, suffix: Option<char>) -> Result<Self, ValidationErrors> {
+        let number = AuditoriumNumber { pavilion, room, suffix };
         number.validate()?;
 
         Ok(number)
     }
+
+    /// Returns the pavilion number.
+    pub fn pavilion(&self) -> u8 {
+        self.pavilion
+    }
+
+    /// Returns the room number.
+    pub fn room(&self) -> u16 {
+        self.room
+    }
+
+    /// Returns the room suffix.
+    pub fn suffix(&self) -> Option<char> {
+        self.suffix
+    }
+
+    /// Returns the pavilion building from the registry.
+    pub fn building(&self) -> Option<&'static Building> {
+        Building::pavilion(self.pavilion)
+    }
 }
 
 impl Display for AuditoriumNumber {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}-{}", self.pavilion, self.room)
+        write!(f, "{}-{}", self.pavilion, self.room)?;
+
+        if let Some(suffix) = self.suffix {
+            write!(f, "{suffix}")?;
+        }
+
+        Ok(())
     }
 }
 
@@ -329,11 +1005,18 @@ impl FromStr for AuditoriumNumber {
     type Err = ScheduleError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if let Some((pavilion, room)) = s.split_once('-') {
-            Ok(AuditoriumNumber::new(
-                pavilion.parse::<u8>().map_err(|_| ScheduleError::InvalidAuditorium(s.to_owned()))?, 
+        if let Some((pavilion, room)) = s.trim().split_once('-') {
+            let room = room.trim();
+            let (room, suffix) = match room.char_indices().last() {
+                Some((index, c)) if c.is_alphabetic() => (&room[..index], Some(c)),
+                _ => (room, None),
+            };
+
+            AuditoriumNumber::with_suffix(
+                pavilion.trim().parse::<u8>().map_err(|_| ScheduleError::InvalidAuditorium(s.to_owned()))?,
                 room.parse::<u16>().map_err(|_| ScheduleError::InvalidAuditorium(s.to_owned()))?,
-            )?)
+                suffix,
+            ).map_err(|errors| ScheduleError::invalid_value(s, errors))
         } else {
             Err(ScheduleError::InvalidAuditorium(s.to_owned()))
         }
@@ -341,8 +1024,8 @@ impl FromStr for AuditoriumNumber {
 }
 
 /// Represents a day of the week of the university schedule.
-#[allow(clippy::missing_docs_in_private_items)]
-#[derive(Default, Clone, Copy, Debug)]
+#[allow(missing_docs, clippy::missing_docs_in_private_items)]
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Day {
     #[default]
     Monday,
@@ -353,6 +1036,18 @@ pub enum Day {
     Saturday,
 }
 
+impl Day {
+    /// All studying days of the week in calendar order.
+    pub const ALL: [Day; 6] = [
+        Day::Monday,
+        Day::Tuesday,
+        Day::Wednesday,
+        Day::Thursday,
+        Day::Friday,
+        Day::Saturday,
+    ];
+}
+
 impl Display for Day {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         use Day::*;
@@ -388,4 +1083,154 @@ impl FromStr for Day {
             _ => Err(ScheduleError::InvalidDayOfWeek(s.to_owned())),
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Parses a label, panicking on errors.
+    fn lesson(label: &str) -> LessonType {
+        LessonType::from_str(label).unwrap()
+    }
+
+    #[test]
+    fn parses_lesson_kinds() {
+        assert_eq!(lesson("лекція").kind, LessonKind::Lection);
+        assert_eq!(lesson("2").kind, LessonKind::Seminar);
+        assert_eq!(lesson("практ. 2").kind, LessonKind::Practical);
+        assert_eq!(lesson("лаб. 1").kind, LessonKind::Lab);
+        assert_eq!(lesson("консультація").kind, LessonKind::Consultation);
+        assert_eq!(lesson("Екзамен").kind, LessonKind::Exam);
+        assert!(LessonType::from_str("англ.").is_err());
+    }
+
+    #[test]
+    fn parses_group_numbers() {
+        assert_eq!(lesson("лекція").groups, Vec::<GroupNumber>::new());
+        assert_eq!(lesson("2 (англ.)").groups, vec![2]);
+        assert_eq!(lesson("1-3").groups, vec![1, 2, 3]);
+        assert_eq!(lesson("3,1").groups, vec![1, 3]);
+        assert_eq!(lesson("1–2, 5").groups, vec![1, 2, 5]);
+        assert!(LessonType::from_str("3-1").is_err());
+    }
+
+    #[test]
+    fn parses_subgroups() {
+        let lesson = lesson("2а");
+        assert_eq!((lesson.groups, lesson.subgroup), (vec![2], Some('а')));
+
+        assert_eq!(self::lesson("лаб. 1б").subgroup, Some('б'));
+        assert_eq!(self::lesson("1ф").subgroup, None);
+        assert_eq!(self::lesson("1пр").groups, vec![1]);
+    }
+
+    #[test]
+    fn stream_numbers_are_not_groups() {
+        let lesson = lesson("Лекція 1п");
+
+        assert_eq!(lesson.kind, LessonKind::Lection);
+        assert!(lesson.is_for_each_group());
+        assert_eq!(lesson.subgroup, None);
+        assert_eq!(lesson.label(), "Лекція 1п");
+    }
+
+    #[test]
+    fn keeps_original_labels() {
+        assert_eq!(lesson("практ.   2").to_string(), "практ. 2");
+        assert_eq!(LessonType::new(LessonKind::Lab, vec![2, 1], None).to_string(), "Лабораторна 1-2");
+        assert_eq!(LessonType::seminar(3).to_string(), "3");
+    }
+
+    /// Parses weeks of a semester of 14 weeks, panicking on errors.
+    fn weeks(s: &str) -> Weeks {
+        Weeks::parse(s, 14).unwrap()
+    }
+
+    #[test]
+    fn parses_week_lists() {
+        assert_eq!(weeks("6"), Weeks::Single(6));
+        assert_eq!(weeks("3 - 14"), Weeks::Range { first: 3, last: 14 });
+        assert_eq!(weeks("1,3-5,7").numbers(), [1, 3, 4, 5, 7]);
+        assert!(Weeks::parse("1-", 14).is_err());
+        assert!(Weeks::parse("перший", 14).is_err());
+    }
+
+    #[test]
+    fn week_patterns_last_until_the_end_of_the_semester() {
+        assert_eq!(weeks("непарні").numbers(), [1, 3, 5, 7, 9, 11, 13]);
+        assert_eq!(weeks("Парні тижні").numbers(), [2, 4, 6, 8, 10, 12, 14]);
+        assert_eq!(weeks("щотижня").numbers(), (1..=14).collect::<Vec<u8>>());
+        assert_eq!(weeks("кожен третій тиждень з 2").numbers(), [2, 5, 8, 11, 14]);
+        assert_eq!(weeks("кожен 5-й тиждень з 1").numbers(), [1, 6, 11]);
+        assert_eq!(Weeks::from_str("непарні").unwrap().numbers().last(), Some(&15));
+        assert!(Weeks::parse("кожен 0-й тиждень з 1", 14).is_err());
+    }
+
+    #[test]
+    fn writes_week_patterns_in_words_when_read_back_the_same() {
+        let every = |first, step, last| Weeks::Every { first, step, last };
+
+        assert_eq!(every(1, 2, 15).to_string(), "непарні");
+        assert_eq!(every(2, 2, 15).to_string(), "парні");
+        assert_eq!(every(1, 5, 15).to_string(), "1,6,11");
+        assert_eq!(weeks("непарні").to_string(), "непарні до 14");
+        assert_eq!(Weeks::pattern_name(2, 4, 15), "кожен четвертий тиждень з 2");
+        assert_eq!(Weeks::pattern_name(1, 5, 12), "кожен 5-й тиждень з 1 до 12");
+        assert_eq!(every(16, 2, 15).to_string(), "кожен другий тиждень з 16");
+
+        for last in 12..=16 {
+            for first in 1..=3 {
+                for step in 1..=7 {
+                    let weeks = every(first, step, last);
+                    let parsed = Weeks::from_str(&weeks.to_string()).unwrap();
+
+                    assert_eq!(parsed.numbers(), weeks.numbers());
+                    assert!(!matches!(parsed, Weeks::Every { .. }) || parsed == weeks);
+                }
+            }
+        }
+
+        assert_eq!(Weeks::from_str(&every(16, 2, 14).to_string()).unwrap(), every(16, 2, 14));
+        assert_eq!(Weeks::parse("парні до 10", 14).unwrap().numbers(), [2, 4, 6, 8, 10]);
+    }
+
+    /// Parses an auditorium and writes it back, panicking on errors.
+    fn auditorium(s: &str) -> String {
+        Auditorium::from_str(s).unwrap().to_string()
+    }
+
+    #[test]
+    fn parses_rooms() {
+        assert_eq!(auditorium("3-205"), "3-205");
+        assert_eq!(auditorium("ауд. 1-225а"), "1-225а");
+        assert_eq!(Auditorium::from_str("1 - 225").unwrap().building().unwrap().name, "Корпус 1");
+        assert!(Auditorium::from_str("12-205").is_err());
+        assert!(Auditorium::from_str("3-").is_err());
+    }
+
+    #[test]
+    fn parses_named_buildings() {
+        assert_eq!(auditorium("КМЦ"), "КМЦ");
+        assert_eq!(auditorium("бібл."), "Бібліотека");
+        assert_eq!(auditorium("с/з"), "Спортзал");
+        assert!(Auditorium::from_str("КМЦ").unwrap().room().is_none());
+    }
+
+    #[test]
+    fn parses_distance_and_hybrid_lessons() {
+        assert_eq!(auditorium("Д"), "Дистанційно");
+        assert_eq!(auditorium("online (Zoom)"), "Дистанційно (Zoom)");
+        assert_eq!(auditorium("Дистанційно https://meet.google.com/abc"), "Дистанційно (https://meet.google.com/abc)");
+        assert_eq!(auditorium("6-204 + Д"), "6-204 + Дистанційно");
+
+        let hybrid = Auditorium::from_str("6-204 + Д").unwrap();
+        assert!(hybrid.is_in_person() && hybrid.is_online());
+    }
+
+    #[test]
+    fn parses_rooms_to_be_announced() {
+        assert_eq!(auditorium("тба"), "ТБА");
+        assert!(Auditorium::from_str("КМЦ + 3-205").is_err());
+    }
+}
@@ -0,0 +1,61 @@
+/// Apostrophes used in Ukrainian words, which are dropped from slugs (e.g. `комп'ютерні`).
+const APOSTROPHES: [char; 4] = ['\'', 'ʼ', '’', '`'];
+
+/// Converts a display name into a slug: lowercase words joined with `-`.
+///
+/// Whitespace, newlines and punctuation only separate words, so cosmetic edits
+/// of the name (e.g. double spaces or line breaks in a spreadsheet cell) keep the slug.
+///
+/// # Arguments
+///
+/// * `name`: The display name (e.g. `Факультет  Інформатики`).
+///
+/// # Returns
+///
+/// The slug (e.g. `факультет-інформатики`).
+pub fn slug(name: &str) -> String {
+    let mut slug = String::with_capacity(name.len());
+
+    for c in name.chars().filter(|c| !APOSTROPHES.contains(c)) {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+
+    if slug.ends_with('-') {
+        slug.pop();
+    }
+
+    slug
+}
+
+/// Computes a content hash identifier of several values.
+///
+/// Whitespace inside the values is normalized before hashing, so the identifier
+/// is stable under cosmetic edits.
+///
+/// # Arguments
+///
+/// * `parts`: The values identifying an item (e.g. discipline, group, day and time of a lesson).
+///
+/// # Returns
+///
+/// The identifier as 16 hexadecimal digits.
+pub fn content_id(parts: &[&str]) -> String {
+    let normalized: Vec<String> = parts
+        .iter()
+        .map(|part| part.split_whitespace().collect::<Vec<&str>>().join(" "))
+        .collect();
+
+    format!("{:016x}", fnv1a(normalized.join("\u{1f}")))
+}
+
+/// Computes the 64-bit FNV-1a hash of a string or bytes, which is stable between runs
+/// and platforms, unlike the standard library hasher.
+pub fn fnv1a<T: AsRef<[u8]>>(data: T) -> u64 {
+    data.as_ref().iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    })
+}
@@ -0,0 +1,312 @@
+/*!
+# NaUKMA Schedule
+
+**FIdo** testing project: Studying schedule parser for National University of Kyiv-Mohyla Academia 
+
+## Usage
+
+```bash
+$ naukma_schedule --files <faculty.speciality.xlsx> <faculty.xlsx>
+```
+
+Use appropriate filenames for spreadsheet files. Example:
+
+* `Факультет Інформатики.Інженерія програмного забезпечення.xlsx` - faculty is **Факультет Інформатики** and speciality is **Інженерія програмного забезпечення**
+* `Факультет Економічних Наук.xlsx` - faculty is **Факультет Економічних Наук**; multiple specialities are defined in the file
+
+The part after the last dot is the speciality only if it names one, so faculty names may contain dots.
+Only the file name is used, so files may reside in any directory. If the file name does not name a faculty
+or a speciality, they are read from the title rows above the `День | Час | ...` header (if any).
+Both can be set explicitly with `--faculty <name>` and `--speciality <name>` flags.
+
+Legacy `.xls`, LibreOffice `.ods` and `.csv` (comma- or semicolon-separated) files are supported as well.
+The format is detected by the file extension or, if it is unknown, by the file content.
+
+Instead of listing every file, pass directories searched recursively with `--dir` or glob patterns
+with `--files`. Lock files (`~$...xlsx`) and other files are skipped, and the faculties and specialities
+found in every file are printed:
+
+```bash
+$ naukma_schedule --dir schedules/2024-1
+$ naukma_schedule html --files "schedules/**/*.xlsx"
+```
+
+Rows which are not lessons are recorded with their sheet and row number. Partially filled rows
+(e.g. a lesson without weeks) are likely data-entry mistakes and are printed as warnings on every run;
+the `report` command lists every skipped row, including blank separators and empty time slots:
+
+```bash
+$ naukma_schedule report --files <files...>
+```
+
+Besides week lists (`1-13`, `1,3-8,10`), the weeks column accepts patterns: `непарні`, `парні`, `щотижня`
+and `кожен другий тиждень з 2` (also `третій`, `четвертий`, or a number: `кожен 5-й тиждень з 1`).
+Patterns last until the end of the semester, `semester_weeks` of the config (15 by default), or until the week
+after `до` (`непарні до 12`). They are written back in words when it is shorter than the list, with `до`
+if the semester is not 15 weeks long, so `schedule.json` is read back the same.
+
+Every row of a discipline is kept by default. With `--merge-duplicates` (or `merge_duplicates = true`
+in the config), identical rows are merged, and rows of a lesson split by weeks (e.g. `1-5` and `6-13`)
+are coalesced into one lesson with combined weeks (`1-13`). Only rows with the same lesson label, day, time
+and auditorium are merged, and only if their weeks are the same, or disjoint and consecutive together.
+Every merge is printed as a note and listed by `report`.
+
+`schedule.json` starts with metadata: the academic year, trimester, course and level of study and the hashes
+of the source files. The generation time is written only with `--timestamp` (or `timestamp = true` in the config),
+or when `SOURCE_DATE_EPOCH` is set, which is used instead of the current time. The metadata is detected
+from file names and title rows (e.g. `Розклад занять 2024-2025 н.р., осінній триместр, 3 курс, бакалаврат`), set in the `[metadata]` section
+of the config, or passed with flags, which take precedence:
+
+```bash
+$ naukma_schedule --academic-year 2024-2025 --term autumn --course 3 --level bachelor --files <files...>
+```
+
+Files are parsed concurrently; the order of faculties in the output follows the order of the files.
+By default the first file which cannot be parsed aborts the run. With `--lenient`, such files are
+reported and skipped, and the rest are parsed as usual. In the library, `source::ScheduleSource` parses
+the inputs the same way for the command line, the watch mode and the server.
+
+In `schedule.json`, a lecture shared by several specialities (e.g. `(екон.+фін.)`) is copied into each of them.
+The `normalized` command writes `schedule.normalized.json`, where every lesson is stored once with the specialities
+attending it, and specialities refer to lessons by identifier; `shared` lists the lessons attended together:
+
+```bash
+$ naukma_schedule normalized --files <files...>
+$ naukma_schedule shared --files <files...>
+```
+
+Render a static HTML timetable of a speciality or a personal selection:
+
+```bash
+$ naukma_schedule html --select-speciality Економіка --output economics.html --files <files...>
+$ naukma_schedule html --discipline "Системне програмування:2" --discipline "Схематотехніка:1" --files <files...>
+```
+
+Print a compact timetable of a week for posting to chats (`--format markdown` or `--format plain`):
+
+```bash
+$ naukma_schedule text --select-speciality Фінанси --week 3 --format plain --files <files...>
+```
+
+Spreadsheets with `Дата | Час | Дисципліна | Група | Аудиторія` columns are parsed as exam sessions.
+The time may be a range (`10:00-11:20`), a start time (`10:00`) or a time cell. Exams listed above the first date
+are skipped with a warning.
+Export them as an HTML table or an iCalendar file; conflicts (e.g. two exams of a speciality on the same day) are reported:
+
+```bash
+$ naukma_schedule exams --format ics --select-speciality Фінанси --output exams.ics --files <files...>
+```
+
+Pass `--compact` to choose a single seminar group of every discipline chosen without groups, preferring
+compact days. Breaks shorter than the walk between buildings are reported as warnings. There is no built-in
+campus map: walking times are read from a TOML (or JSON) file passed with `--config`, and short breaks
+are not checked without them:
+
+```toml
+[[campus.walks]]
+from = "1"        # building codes: pavilion numbers, `КМЦ`, `Бібліотека`, `Спортзал`
+to = "КМЦ"
+minutes = 5
+```
+
+```bash
+$ naukma_schedule text --compact --config campus.toml -d "Системне програмування" -d Схематотехніка --files <files...>
+```
+
+The `lint` command checks the schedule semantics. Every diagnostic has a rule ID and a severity;
+the command fails if any error is found. Rules: `lecture-with-group`, `weeks-beyond-semester`, `late-saturday`,
+`duplicate-lesson`, `seminar-without-lecture`, `lecture-without-seminars`, `nonstandard-duration` and
+`distance-next-to-in-person`. They are switched on or off in the `[lint]` section of the config;
+weeks after `semester_weeks` are reported by `weeks-beyond-semester`. `duplicate-lesson` reports rows listing
+the same lesson twice in the same weeks; with `--merge-duplicates`, the lint runs after merging,
+so only the rows which were not merged (e.g. with overlapping weeks) are reported:
+
+```toml
+semester_weeks = 15   # the default
+
+[lint]
+lesson_minutes = 80   # the default
+
+[lint.rules]
+late-saturday = false
+```
+
+```bash
+$ naukma_schedule lint --config config.toml --files <files...>
+```
+
+A student profile (TOML or JSON) keeps personal enrolments, so they need not be repeated in every command.
+Every command accepts `--profile`; the profile is checked against the schedule, reporting renamed disciplines
+and missing groups. Selection flags take precedence over the profile:
+
+```toml
+faculty = "Факультет Інформатики"
+speciality = "Інженерія програмного забезпечення"
+semester_start = "02.09.2024"   # any day of the first studying week
+
+[[disciplines]]
+name = "Системне програмування"
+groups = [2]
+
+[[disciplines]]
+name = "Схематотехніка"         # every group if `groups` are omitted
+```
+
+```bash
+$ naukma_schedule html --profile me.toml --files <files...>
+$ naukma_schedule ics --profile me.toml --output lessons.ics --files <files...>   # an event per lesson occurrence
+$ naukma_schedule conflicts --profile me.toml --files <files...>                  # overlaps, short breaks, exams
+$ naukma_schedule next --profile me.toml --files <files...>                       # or `--at "15.01.2024 10:30"`
+```
+
+Serve the schedule to web frontends and bots with a local HTTP JSON API. Input files may be spreadsheets
+or previously written `schedule.json` files; the schedule is parsed again, exactly like on start, when they change
+or files are added to the `--dir` directories or the glob patterns (checked at most every two seconds):
+
+```bash
+$ naukma_schedule serve --address 127.0.0.1:8080 --profiles profiles/ --files <files...>
+```
+
+Endpoints: `/schedule`, `/faculties`, `/specialities?faculty=`, `/disciplines?faculty=&speciality=`,
+`/lessons?faculty=&speciality=&week=&day=&discipline=&compact`, `/free-rooms?day=&time=&week=`
+and `/profiles/<name>.ics` (an iCalendar feed of `profiles/<name>.toml`).
+Errors are JSON objects with a message; schedule errors also have a machine-readable `code`
+(e.g. `{"error": "...", "code": "invalid-profile"}`), which `ScheduleError::code` returns in the library.
+
+Pass `--watch` to any command to parse the input files again on every save (or added file). Parse errors, warnings,
+a summary and the differences from the previous successful parse are printed without restarting:
+
+```bash
+$ naukma_schedule --watch text --output timetable.md --files <files...>
+```
+
+Write a normalized workbook per faculty (one sheet per speciality, including specialities without lessons),
+which parses back to the same schedule. Faculties parsed from a file per speciality share a workbook:
+
+```bash
+$ naukma_schedule xlsx --output-dir cleaned --files <files...>
+```
+
+## Library usage
+
+Spreadsheets can be parsed from any reader with explicitly supplied metadata,
+so no temporary files are needed:
+
+```no_run
+use std::io::Cursor;
+use naukma_schedule::schedule::{Schedule, FacultyMeta, SpecialityName};
+
+# fn upload() -> Vec<u8> { vec![] }
+let bytes: Vec<u8> = upload();
+let meta = FacultyMeta {
+    name: "Факультет Інформатики".to_owned(),
+    speciality: Some(SpecialityName::SoftwareEngineering),
+};
+
+let schedule = Schedule::from_sources([(Cursor::new(bytes), meta)])?;
+# Ok::<(), naukma_schedule::error::ScheduleError>(())
+```
+
+## Features
+* Schedule fields (de-)serialization and validation
+* Nested schedule structure
+* Normalized schedule storing lectures shared by specialities once
+* Lesson types: lections, seminars, practicals, labs, consultations, exams and tests,
+  merged groups (`1-2`, `1,3`), sub-groups (`2а`) and streams (`Лекція 1п`), keeping the original labels
+* Auditoriums: a registry of buildings (pavilions, КМЦ, library, sports hall), room suffixes (`1-225а`),
+  distance lessons with meeting links, hybrid lessons (`6-204 + Д`) and rooms to be announced (`тба` or an empty cell)
+* Deterministic output: regenerating from unchanged spreadsheets gives a byte-identical `schedule.json`
+  (the optional generation time is taken from `SOURCE_DATE_EPOCH` if set)
+* Schedule metadata: academic year, trimester, course, level, optional generation time and source file hashes
+* Stable identifiers (`Ідентифікатор`): slugs of faculty, speciality and discipline names and content hashes
+  of lesson occurrences (including the auditorium), unaffected by whitespace edits and used as iCalendar event UIDs
+* Static HTML timetable export with a week selector
+* Markdown and plain-text timetables for chats
+* Configurable campus map with walking times: short break warnings and compact personal timetables
+* Student profiles: personal timetables, iCalendar lesson feeds, conflict reports and the next lesson
+* Local HTTP JSON API with hot reload, free rooms search and personal iCalendar feeds
+* Watch mode parsing spreadsheets again on every save and reporting the differences
+* Normalized xlsx workbook export
+* Exam session schedules with conflict checks, HTML and iCalendar export
+* `.xlsx`, `.xls`, `.ods` and `.csv` input files
+* Merged cells of `.xlsx`, `.xls` and `.ods` workbooks: merged disciplines, weeks and auditoriums apply to every
+  cell they cover, horizontally merged header cells count as one column unless the extra columns have values
+* Concurrent parsing of many workbooks, with a lenient mode skipping broken files
+* Recursive directory search and glob patterns of input files
+* Parse report of skipped rows with warnings about partially filled ones
+* Odd, even, weekly and alternating week patterns bounded by the semester length
+* Optional merging of duplicate rows and of rows split by weeks, with a report of every merge
+* Configurable semantic checks (lint rules) with rule IDs and severities
+* Detailed error messages (e.g. ``minutes must be 0..59, got 75 in `13:75` ``) with error codes
+* Parsing from in-memory spreadsheets (e.g. uploaded files) as a library
+
+## Used crates
+- `anyhow` - flexible pretty error handling
+- `calamine` - xlsx, xls and ods spreadsheet parser
+- `chrono` - current local time
+- `clap` - command line argument parser
+- `csv` - CSV files reader
+- `glob` - input file patterns
+- `rayon` - concurrent files parsing
+- `rust_xlsxwriter` - xlsx spreadsheet writer
+- `serde` - powerful (de-)serialization framework
+- `serde_json` - JSON serialization for serde
+- `thiserror` - dedicated error types design
+- `tiny_http` - local HTTP server
+- `toml` - configuration files parser
+- `validator` - struct fields validation functions
+
+## License
+
+This project is licensed under Unlicense license and is in the **public domain**
+
+Copyright (c) Oleksandr Hnutov
+
+*/
+
+#![warn(missing_docs)]
+#![warn(clippy::missing_docs_in_private_items)]
+
+/// Definitions related to university disciplines' lesson groups.
+pub mod group;
+/// Stable identifiers of schedule elements.
+pub mod id;
+/// Registry of university buildings.
+pub mod building;
+/// Campus map and walking times between buildings.
+pub mod campus;
+/// User configuration files.
+pub mod config;
+/// Student profiles with personal enrolments.
+pub mod profile;
+/// Custom error types and error handling for the university schedule parser.
+pub mod error;
+/// Custom macros for parsing university schedule
+pub mod macros;
+/// Definitions related to the university schedule, including faculties and specialities.
+pub mod schedule;
+/// Academic year, term and source metadata of schedules.
+pub mod metadata;
+/// Normalized schedules storing lessons shared by specialities once.
+pub mod normalized;
+/// Exam/credit session schedules.
+pub mod exam;
+/// Reports of rows skipped while parsing.
+pub mod report;
+/// Summaries and differences of parsed schedules.
+pub mod diff;
+/// Semantic checks of parsed schedules.
+pub mod lint;
+/// Input files of schedules and parsing them with the configuration.
+pub mod source;
+/// Flattened timetables of selected lessons.
+pub mod timetable;
+/// Renderers of timetables into publishable formats.
+pub mod render;
+/// Local HTTP API server.
+pub mod server;
+/// Reading of spreadsheet files in various formats.
+pub mod spreadsheet;
+/// Canonical xlsx workbook writer.
+pub mod writer;
+
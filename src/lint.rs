@@ -0,0 +1,399 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::Config,
+    group::*,
+    schedule::*,
+};
+
+/// Represents the severity of a lint diagnostic.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The schedule is most likely wrong (e.g. a lesson listed twice).
+    Error,
+    /// The schedule is suspicious and should be checked (e.g. a nonstandard lesson duration).
+    Warning,
+    /// The schedule is unusual, but may be correct (e.g. a discipline without seminars).
+    Info,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let stringed = match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "info",
+        };
+
+        write!(f, "{stringed}")
+    }
+}
+
+/// Represents a semantic check of the schedule, identified by its ID in diagnostics and configs.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum Rule {
+    /// A lecture has group numbers, which usually belong to seminars.
+    LectureWithGroup,
+    /// A lesson takes place in weeks after the end of the semester.
+    WeeksBeyondSemester,
+    /// A lesson on Saturday ends after 18:00.
+    LateSaturday,
+    /// The same group of a discipline (with the same label) is listed twice at the same time in the same weeks.
+    ///
+    /// Checked after duplicate rows are merged, if merging is switched on, so it reports
+    /// only the rows which were not merged, e.g. with overlapping weeks.
+    DuplicateLesson,
+    /// A discipline has seminars or practicals, but no lecture.
+    SeminarWithoutLecture,
+    /// A discipline has lectures, but no seminars, practicals or labs.
+    LectureWithoutSeminars,
+    /// A lesson lasts longer or shorter than a standard lesson.
+    NonstandardDuration,
+    /// A distance lesson directly precedes or follows an in-person lesson of the same group.
+    DistanceNextToInPerson,
+}
+
+impl Rule {
+    /// Every rule, in the order of checking.
+    pub const ALL: [Rule; 8] = [
+        Rule::LectureWithGroup,
+        Rule::WeeksBeyondSemester,
+        Rule::LateSaturday,
+        Rule::DuplicateLesson,
+        Rule::SeminarWithoutLecture,
+        Rule::LectureWithoutSeminars,
+        Rule::NonstandardDuration,
+        Rule::DistanceNextToInPerson,
+    ];
+
+    /// Returns the ID of the rule, as written in diagnostics and configs.
+    pub fn id(&self) -> &'static str {
+        use Rule::*;
+
+        match self {
+            LectureWithGroup => "lecture-with-group",
+            WeeksBeyondSemester => "weeks-beyond-semester",
+            LateSaturday => "late-saturday",
+            DuplicateLesson => "duplicate-lesson",
+            SeminarWithoutLecture => "seminar-without-lecture",
+            LectureWithoutSeminars => "lecture-without-seminars",
+            NonstandardDuration => "nonstandard-duration",
+            DistanceNextToInPerson => "distance-next-to-in-person",
+        }
+    }
+
+    /// Returns the severity of the diagnostics of the rule.
+    pub fn severity(&self) -> Severity {
+        use Rule::*;
+
+        match self {
+            WeeksBeyondSemester | DuplicateLesson => Severity::Error,
+            LectureWithGroup | LateSaturday | NonstandardDuration | DistanceNextToInPerson => Severity::Warning,
+            SeminarWithoutLecture | LectureWithoutSeminars => Severity::Info,
+        }
+    }
+}
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.id())
+    }
+}
+
+/// Represents the `[lint]` section of the configuration.
+///
+/// ```toml
+/// [lint]
+/// lesson_minutes = 80
+///
+/// [lint.rules]
+/// late-saturday = false
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct LintConfig {
+    /// The duration of a standard lesson in minutes.
+    pub lesson_minutes: u16,
+    /// Rules switched on (`true`) or off (`false`). Rules not listed are on.
+    pub rules: BTreeMap<Rule, bool>,
+}
+
+impl LintConfig {
+    /// Checks whether the rule is switched on.
+    pub fn is_enabled(&self, rule: Rule) -> bool {
+        self.rules.get(&rule).copied().unwrap_or(true)
+    }
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        LintConfig {
+            lesson_minutes: 80,
+            rules: BTreeMap::new(),
+        }
+    }
+}
+
+/// Represents a problem found by a lint rule.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    /// The rule which found the problem.
+    pub rule: Rule,
+    /// The name of the faculty.
+    pub faculty: String,
+    /// The speciality.
+    pub speciality: SpecialityName,
+    /// The full discipline name.
+    pub discipline: String,
+    /// The description of the problem.
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// Returns the severity of the diagnostic.
+    pub fn severity(&self) -> Severity {
+        self.rule.severity()
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}[{}] {} / {} / {}: {}",
+            self.severity(), self.rule, self.faculty, self.speciality, self.discipline.trim(), self.message,
+        )
+    }
+}
+
+/// Checks the schedule with every rule switched on in the config.
+///
+/// # Arguments
+///
+/// * `schedule`: The parsed schedule.
+/// * `config`: The configuration with the semester length and the lint settings.
+///
+/// # Returns
+///
+/// Diagnostics of every found problem, sorted by severity.
+pub fn lint(schedule: &Schedule, config: &Config) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+
+    for faculty in &schedule.faculties {
+        for (speciality, spec) in &faculty.specialities {
+            let mut report = |rule: Rule, discipline: &str, message: String| {
+                if config.lint.is_enabled(rule) {
+                    diagnostics.push(Diagnostic {
+                        rule,
+                        faculty: faculty.name.clone(),
+                        speciality: *speciality,
+                        discipline: discipline.to_owned(),
+                        message,
+                    });
+                }
+            };
+
+            for (name, discipline) in &spec.disciplines {
+                for rule in Rule::ALL {
+                    for message in check_groups(rule, &discipline.groups, config) {
+                        report(rule, name, message);
+                    }
+                }
+            }
+
+            for (rule, name, message) in check_kinds(spec) {
+                report(rule, name, message);
+            }
+
+            for (name, message) in check_neighbours(spec) {
+                report(Rule::DistanceNextToInPerson, name, message);
+            }
+        }
+    }
+
+    diagnostics.sort_by_key(Diagnostic::severity);
+    diagnostics
+}
+
+/// Checks the groups of a single discipline with a rule, skipping rules which need other disciplines.
+fn check_groups(rule: Rule, groups: &[Group], config: &Config) -> Vec<String> {
+    /// The latest end of a Saturday lesson, in minutes of the day (18:00).
+    const SATURDAY_END: u16 = 18 * 60;
+
+    let describe = |group: &Group| format!("`{}` ({} {})", group.name, group.day, group.time);
+
+    match rule {
+        Rule::LectureWithGroup => groups
+            .iter()
+            .filter(|group| group.name.kind == LessonKind::Lection && !group.name.is_for_each_group())
+            .map(|group| format!("lecture {} has group numbers", describe(group)))
+            .collect(),
+        Rule::WeeksBeyondSemester => groups
+            .iter()
+            .filter(|group| {
+                let weeks = group.weeks.numbers();
+                weeks.first() == Some(&0) || weeks.last().is_some_and(|last| *last > config.semester_weeks)
+            })
+            .map(|group| format!(
+                "{} takes place in weeks {}, but the semester has {} weeks",
+                describe(group), group.weeks, config.semester_weeks,
+            ))
+            .collect(),
+        Rule::LateSaturday => groups
+            .iter()
+            .filter(|group| group.day == Day::Saturday && group.time.to.minutes_of_day() > SATURDAY_END)
+            .map(|group| format!("{} ends after 18:00 on Saturday", describe(group)))
+            .collect(),
+        Rule::DuplicateLesson => {
+            let mut messages = vec![];
+
+            for (index, group) in groups.iter().enumerate() {
+                let duplicate = groups[..index].iter().any(|other| {
+                    other.name == group.name
+                        && other.name.label() == group.name.label()
+                        && other.day == group.day
+                        && other.time == group.time
+                        && other.weeks.overlaps(&group.weeks)
+                });
+
+                if duplicate {
+                    messages.push(format!("{} is listed twice", describe(group)));
+                }
+            }
+
+            messages
+        },
+        Rule::NonstandardDuration => groups
+            .iter()
+            .filter(|group| group.time.duration() != config.lint.lesson_minutes)
+            .map(|group| format!(
+                "{} lasts {} minutes instead of {}",
+                describe(group), group.time.duration(), config.lint.lesson_minutes,
+            ))
+            .collect(),
+        Rule::SeminarWithoutLecture | Rule::LectureWithoutSeminars | Rule::DistanceNextToInPerson => vec![],
+    }
+}
+
+/// Checks the lesson kinds of every discipline of a speciality.
+///
+/// Lectures and seminars of a discipline may be written in separate rows with different
+/// teachers, so the disciplines are matched by their short names.
+fn check_kinds(speciality: &Speciality) -> Vec<(Rule, &str, String)> {
+    let mut kinds: BTreeMap<&str, (&str, Vec<LessonKind>)> = BTreeMap::new();
+
+    for (name, discipline) in &speciality.disciplines {
+        let entry = kinds.entry(Discipline::short_name(name)).or_insert((name, vec![]));
+        entry.1.extend(discipline.groups.iter().map(|group| group.name.kind));
+    }
+
+    let mut messages = vec![];
+
+    for (name, kinds) in kinds.values() {
+        let has_lecture = kinds.contains(&LessonKind::Lection);
+        let has_seminar = kinds
+            .iter()
+            .any(|kind| matches!(kind, LessonKind::Seminar | LessonKind::Practical | LessonKind::Lab));
+
+        if has_seminar && !has_lecture {
+            messages.push((Rule::SeminarWithoutLecture, *name, "seminars without a lecture".to_owned()));
+        } else if has_lecture && !has_seminar {
+            messages.push((Rule::LectureWithoutSeminars, *name, "lectures without seminars".to_owned()));
+        }
+    }
+
+    messages
+}
+
+/// Finds distance lessons directly before or after in-person lessons of the same group of a speciality,
+/// which leave no time to get home or to the university.
+fn check_neighbours(speciality: &Speciality) -> Vec<(&str, String)> {
+    /// The longest break between lessons in consecutive time slots, in minutes.
+    const ADJACENT_BREAK: u16 = 30;
+
+    let lessons: Vec<(&str, &Group)> = speciality.disciplines
+        .iter()
+        .flat_map(|(name, discipline)| discipline.groups.iter().map(move |group| (name.as_str(), group)))
+        .collect();
+
+    let is_distance = |group: &Group| group.auditorium.is_online() && !group.auditorium.is_in_person();
+    let is_in_person = |group: &Group| group.auditorium.is_in_person() && !group.auditorium.is_online();
+    let share_group = |a: &LessonType, b: &LessonType| {
+        a.is_for_each_group() || b.is_for_each_group() || a.groups.iter().any(|number| b.groups.contains(number))
+    };
+
+    let mut messages = vec![];
+
+    for (name, distance) in lessons.iter().filter(|(_, group)| is_distance(group)) {
+        for (other_name, other) in lessons.iter().filter(|(_, group)| is_in_person(group)) {
+            let adjacent = distance.time.break_until(&other.time)
+                .or_else(|| other.time.break_until(&distance.time))
+                .is_some_and(|minutes| minutes <= ADJACENT_BREAK);
+
+            if distance.day == other.day
+                && adjacent
+                && distance.weeks.overlaps(&other.weeks)
+                && share_group(&distance.name, &other.name)
+            {
+                messages.push((*name, format!(
+                    "distance lesson `{}` ({} {}) is next to in-person `{}` `{}` ({}, {})",
+                    distance.name,
+                    distance.day,
+                    distance.time,
+                    Discipline::short_name(other_name),
+                    other.name,
+                    other.time,
+                    other.auditorium,
+                )));
+            }
+        }
+    }
+
+    messages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates a group from the lesson type, day, time and weeks, as written in spreadsheets.
+    fn group(name: &str, day: &str, time: &str, weeks: &str) -> Group {
+        Group {
+            id: String::new(),
+            name: name.parse().unwrap(),
+            time: time.parse().unwrap(),
+            weeks: weeks.parse().unwrap(),
+            auditorium: "1-225".parse().unwrap(),
+            day: day.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn reports_lessons_listed_twice() {
+        let groups = [
+            group("1", "Понеділок", "08:30-09:50", "1-7"),
+            group("1", "Понеділок", "08:30-09:50", "5-13"),
+        ];
+        let messages = check_groups(Rule::DuplicateLesson, &groups, &Config::default());
+
+        assert_eq!(messages, ["`1` (Понеділок 08:30-09:50) is listed twice"]);
+    }
+
+    #[test]
+    fn lessons_in_other_weeks_or_with_other_labels_are_not_duplicates() {
+        let groups = [
+            group("практ. 1", "Понеділок", "08:30-09:50", "1-7"),
+            group("практ. 1", "Понеділок", "08:30-09:50", "8-13"),
+            group("Практична 1", "Понеділок", "08:30-09:50", "1-13"),
+        ];
+
+        assert!(check_groups(Rule::DuplicateLesson, &groups, &Config::default()).is_empty());
+    }
+}
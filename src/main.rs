@@ -1,78 +1,346 @@
-/*!
-# NaUKMA Schedule
+//! Command-line interface of the NaUKMA schedule parser.
+//!
+//! See the library documentation for the usage and features.
 
-**FIdo** testing project: Studying schedule parser for National University of Kyiv-Mohyla Academia 
+#![warn(missing_docs)]
+#![warn(clippy::missing_docs_in_private_items)]
+
+/// Command-line interface definitions.
+mod cli;
+
+use std::{str::FromStr, time::Duration};
+
+use anyhow::{anyhow, bail, Result};
+use chrono::{Datelike, Timelike};
+use cli::{Args, Command, ExamsFormat};
+use naukma_schedule::{
+    config::Config,
+    diff::{ScheduleDiff, Summary},
+    exam::Date,
+    group::Time,
+    lint::{self, Severity},
+    normalized::NormalizedSchedule,
+    profile::Profile,
+    render,
+    report::ParseReport,
+    server::Server,
+    spreadsheet::modification_times,
+    writer,
+    schedule::*,
+    timetable::{Timetable, ExamEntry},
+};
 
-## Usage
+/// The note printed when short breaks cannot be checked.
+const NO_CAMPUS: &str = "short breaks are not checked: no walking times between buildings are configured (`[[campus.walks]]` in `--config`)";
+
+/// The entry point of the university schedule parser program.
+///
+/// This function parses command-line arguments, reads university schedule data from Excel files,
+/// and writes the parsed data to a JSON file.
+///
+/// # Returns
+///
+/// A `Result` indicating success or failure of the program.
+fn main() -> Result<()> {
+    let args = Args::parse_checked();
 
-```bash
-$ naukma_schedule --files <faculty.speciality.xlsx> <faculty.xlsx>
-```
+    if args.watch {
+        return watch(&args);
+    }
 
-Use appropriate filenames for spreadsheet files. Example:
+    let schedule = parse(&args)?;
 
-* `Факультет Інформатики.Інженерія програмного забезпечення.xlsx` - faculty is **Факультет Інформатики** and speciality is **Інженерія програмного забезпечення**
-* `Факультет Економічних Наук.xlsx` - faculty is **Факультет Економічних Наук**; multiple specialities are defined in the file
+    execute(&args, schedule)
+}
 
-## Features
-* Schedule fields (de-)serialization and validation
-* Nested schedule structure
+/// Parses the input files, skipping and reporting the ones which cannot be parsed in lenient mode.
+/// Prints which faculties were parsed from which file if the files were discovered,
+/// partially filled rows and merged duplicate rows.
+fn parse(args: &Args) -> Result<Schedule> {
+    let config = args.config()?;
 
-## Used crates
-- `anyhow` - flexible pretty error handling
-- `calamine` - xlsx spreadsheet parser
-- `clap` - command line argument parser
-- `serde` - powerful (de-)serialization framework
-- `serde_json` - JSON serialization for serde
-- `thiserror` - dedicated error types design
-- `validator` - struct fields validation functions
+    let source = args.source();
+    let loaded = source.load(&config)?;
 
-## License
+    if source.is_discovered() {
+        for (path, contents) in &loaded.parsed {
+            eprintln!("{}: {contents}", path.display());
+        }
+    }
 
-This project is licensed under Unlicense license and is in the **public domain**
+    for (path, e) in loaded.skipped {
+        eprintln!("Skipping `{}`: {:#}", path.display(), anyhow::Error::from(e));
+    }
 
-Copyright (c) Oleksandr Hnutov
+    let schedule = loaded.schedule;
+    let report = ParseReport::of(&schedule);
 
-*/
+    for (faculty, row) in report.suspicious() {
+        eprintln!("Warning: {faculty}: {row}");
+    }
 
-#![warn(missing_docs)]
-#![warn(clippy::missing_docs_in_private_items)]
+    for (faculty, merges) in &report.merged {
+        for merge in merges {
+            eprintln!("Note: {faculty}: {merge}");
+        }
+    }
 
-/// Definitions related to university disciplines' lesson groups.
-mod group;
-/// Custom error types and error handling for the university schedule parser.
-mod error;
-/// Custom macros for parsing university schedule
-mod macros;
-/// Definitions related to the university schedule, including faculties and specialities.
-mod schedule;
-
-use anyhow::Result;
-use clap::Parser;
-use schedule::*;
-
-/// The command-line arguments parsing structure.
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// The list of file paths to university schedule Excel files.
-    #[arg(short, long, required=true, num_args=1..)]
-    files: Vec<String>,
+    Ok(schedule)
 }
 
-/// The entry point of the university schedule parser program.
+/// Validates the parsed schedule against the student profile and performs the command.
 ///
-/// This function parses command-line arguments, reads university schedule data from Excel files,
-/// and writes the parsed data to a JSON file.
+/// # Arguments
+///
+/// * `args`: The command-line arguments.
+/// * `schedule`: The schedule parsed from the input files.
 ///
 /// # Returns
 ///
-/// A `Result` indicating success or failure of the program.
-fn main() -> Result<()> {
-    let args = Args::parse();
-    let schedule = Schedule::new(&args.files)?;
+/// A `Result` indicating success or failure of the command.
+fn execute(args: &Args, schedule: Schedule) -> Result<()> {
+    let config = args.config()?;
+    let profile = args.profile()?;
+
+    if let Some(profile) = &profile {
+        profile.validate(&schedule)?;
+    }
+
+    let profile = profile.as_ref();
+
+    match &args.command {
+        None => {
+            std::fs::write("schedule.json", serde_json::to_string_pretty(&schedule).unwrap())?;
+        },
+        Some(Command::Html { output, selection }) => {
+            let timetable = selection.timetable(&schedule, profile, &config.campus);
+            warn_tight_breaks(&timetable, &config);
+            std::fs::write(output, render::html::render(&timetable, &selection.title(profile)))?;
+        },
+        Some(Command::Text { format, output, selection }) => {
+            let timetable = selection.timetable(&schedule, profile, &config.campus);
+            warn_tight_breaks(&timetable, &config);
+            let text = render::text::render(&timetable, &selection.title(profile), *format);
+
+            match output {
+                Some(path) => std::fs::write(path, text)?,
+                None => print!("{text}"),
+            }
+        },
+        Some(Command::Exams { format, output, selection }) => {
+            for session in &schedule.exam_sessions {
+                for conflict in session.conflicts() {
+                    eprintln!("{}: {conflict}", session.faculty);
+                }
+            }
+
+            let exams = ExamEntry::select(&schedule, &selection.selection(profile));
+            let content = match *format {
+                ExamsFormat::Html => render::html::render_exams(&exams, &selection.title(profile)),
+                ExamsFormat::Ics => render::ics::render_exams(&exams, &selection.title(profile)),
+            };
+
+            std::fs::write(output, content)?;
+        },
+        Some(Command::Xlsx { output_dir }) => {
+            writer::write_schedule(&schedule, output_dir)?;
+        },
+        Some(Command::Ics { output, semester_start, selection }) => {
+            let semester_start = semester_start_of(*semester_start, profile)?;
+            let timetable = selection.timetable(&schedule, profile, &config.campus);
+            let content = render::ics::render_lessons(&timetable, semester_start, &selection.title(profile));
+
+            std::fs::write(output, content)?;
+        },
+        Some(Command::Conflicts { selection }) => {
+            let timetable = selection.timetable(&schedule, profile, &config.campus);
+            let exams = ExamEntry::select(&schedule, &selection.selection(profile));
+            let mut found = false;
+
+            for clash in timetable.clashes() {
+                println!("{clash}");
+                found = true;
+            }
+
+            if config.campus.is_empty() {
+                eprintln!("Note: {NO_CAMPUS}");
+            }
+
+            for tight in timetable.tight_breaks(&config.campus) {
+                println!("{tight}");
+                found = true;
+            }
+
+            for session in &schedule.exam_sessions {
+                let selected = |discipline: &str| exams
+                    .iter()
+                    .any(|entry| entry.faculty == session.faculty && entry.exam.discipline == discipline);
+
+                for conflict in session.conflicts() {
+                    let (first, second) = conflict.disciplines();
 
-    std::fs::write("schedule.json", serde_json::to_string_pretty(&schedule).unwrap())?;
+                    if selected(first) && selected(second) {
+                        println!("{}: {conflict}", session.faculty);
+                        found = true;
+                    }
+                }
+            }
+
+            if !found {
+                println!("No conflicts found");
+            }
+        },
+        Some(Command::Serve { address, profiles }) => {
+            let mut server = Server::with_schedule(schedule, args.source(), config, profiles.clone());
+
+            eprintln!("Listening on http://{address}");
+            server.run(address)?;
+        },
+        Some(Command::Report) => {
+            print!("{}", ParseReport::of(&schedule));
+        },
+        Some(Command::Normalized { output }) => {
+            let normalized = NormalizedSchedule::of(&schedule);
+            std::fs::write(output, serde_json::to_string_pretty(&normalized)?)?;
+        },
+        Some(Command::Lint) => {
+            let diagnostics = lint::lint(&schedule, &config);
+            let errors = diagnostics.iter().filter(|d| d.severity() == Severity::Error).count();
+
+            for diagnostic in &diagnostics {
+                println!("{diagnostic}");
+            }
+
+            if diagnostics.is_empty() {
+                println!("No problems found");
+            } else if errors > 0 {
+                bail!("{errors} errors found");
+            }
+        },
+        Some(Command::Shared) => {
+            for faculty in NormalizedSchedule::of(&schedule).faculties {
+                for lesson in faculty.shared() {
+                    println!("{}: {lesson}", faculty.name);
+                }
+            }
+        },
+        Some(Command::Next { at, semester_start, selection }) => {
+            let semester_start = semester_start_of(*semester_start, profile)?;
+            let (date, time) = match at {
+                Some(at) => parse_moment(at)?,
+                None => now()?,
+            };
+
+            let timetable = selection.timetable(&schedule, profile, &config.campus);
+
+            match timetable.next_lesson(semester_start, date, time) {
+                Some((date, lesson)) => println!(
+                    "{date} ({}) {} {} — {}, {}",
+                    lesson.group.day,
+                    lesson.group.time,
+                    lesson.discipline,
+                    lesson.group.name,
+                    lesson.group.auditorium,
+                ),
+                None => println!("No more lessons this semester"),
+            }
+        },
+    }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Parses the input files again on every change, printing parse errors, a summary
+/// and the differences from the previous successful parse, and performs the command.
+///
+/// # Arguments
+///
+/// * `args`: The command-line arguments.
+///
+/// # Returns
+///
+/// An error if the command cannot be watched. Otherwise runs until the process is stopped.
+fn watch(args: &Args) -> Result<()> {
+    /// The interval between checks of the input files.
+    const INTERVAL: Duration = Duration::from_millis(500);
+
+    if matches!(args.command, Some(Command::Serve { .. })) {
+        bail!("`serve` reloads the schedule by itself, `--watch` is not needed");
+    }
+
+    let source = args.source();
+    let mut previous: Option<Schedule> = None;
+    let mut modified = None;
+
+    loop {
+        // Files added to the directories or matching the patterns are picked up too
+        let files = source.resolve().unwrap_or_default();
+        let current = (files.clone(), modification_times(&files));
+
+        if modified.as_ref() != Some(&current) {
+            modified = Some(current);
+            eprintln!("--- Parsing {} files ---", files.len());
+
+            match parse(args) {
+                Ok(schedule) => {
+                    eprintln!("{}", Summary::of(&schedule));
+
+                    if let Some(previous) = &previous {
+                        eprint!("{}", ScheduleDiff::between(previous, &schedule));
+                    }
+
+                    if let Err(e) = execute(args, schedule.clone()) {
+                        eprintln!("Error: {e:#}");
+                    }
+
+                    previous = Some(schedule);
+                },
+                Err(e) => eprintln!("Error: {e:#}"),
+            }
+
+            eprintln!("--- Watching for changes ---");
+        }
+
+        std::thread::sleep(INTERVAL);
+    }
+}
+
+/// Prints warnings about breaks too short to walk between buildings to the standard error,
+/// or a note that they are not checked without walking times in the configuration.
+fn warn_tight_breaks(timetable: &Timetable, config: &Config) {
+    if config.campus.is_empty() {
+        eprintln!("Note: {NO_CAMPUS}");
+        return;
+    }
+
+    for tight in timetable.tight_breaks(&config.campus) {
+        eprintln!("Warning: {tight}");
+    }
+}
+
+/// Returns the semester start passed explicitly or set in the student profile.
+fn semester_start_of(semester_start: Option<Date>, profile: Option<&Profile>) -> Result<Date> {
+    semester_start
+        .or(profile.and_then(|p| p.semester_start))
+        .ok_or_else(|| anyhow!("The semester start is unknown: pass `--semester-start` or set `semester_start` in the profile"))
+}
+
+/// Parses a moment in `dd.mm.yyyy HH:MM` format.
+fn parse_moment(s: &str) -> Result<(Date, Time)> {
+    let (date, time) = s
+        .trim()
+        .split_once(' ')
+        .ok_or_else(|| anyhow!("Invalid moment: `{s}`. Example: `15.01.2024 10:30`"))?;
+
+    Ok((Date::from_str(date)?, Time::from_str(time.trim())?))
+}
+
+/// Returns the current local date and time.
+fn now() -> Result<(Date, Time)> {
+    let now = chrono::Local::now();
+
+    Ok((
+        Date::new(now.year() as u16, now.month() as u8, now.day() as u8)?,
+        Time::new(now.hour() as u8, now.minute() as u8)?,
+    ))
+}
@@ -0,0 +1,338 @@
+use std::{
+    fmt::Display,
+    path::Path,
+    str::FromStr,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    id::fnv1a,
+    macros::impl_serde_display_fromstr,
+    error::{ScheduleError, ScheduleResult},
+};
+
+/// Represents the metadata of a schedule: the academic year, term, course and level it belongs to,
+/// when it was generated and from which files.
+///
+/// Fields come from command-line flags, the `[metadata]` section of the configuration,
+/// or file names and title rows of the spreadsheets (e.g. `2024-2025 н.р., осінній триместр, 3 курс`).
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+pub struct Metadata {
+    /// The academic year (e.g. `2024-2025`).
+    #[serde(rename = "Навчальний рік", alias = "academic_year", default, skip_serializing_if = "Option::is_none")]
+    pub academic_year: Option<AcademicYear>,
+    /// The trimester.
+    #[serde(rename = "Триместр", alias = "term", default, skip_serializing_if = "Option::is_none")]
+    pub term: Option<Term>,
+    /// The year of study.
+    #[serde(rename = "Курс", alias = "course", default, skip_serializing_if = "Option::is_none")]
+    pub course: Option<u8>,
+    /// The level of study.
+    #[serde(rename = "Рівень", alias = "level", default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<Level>,
+    /// The UTC time of generation in `YYYY-MM-DDTHH:MM:SSZ` format, set only on request (see `Metadata::stamp`).
+    #[serde(rename = "Згенеровано", default, skip_serializing_if = "Option::is_none")]
+    pub generated_at: Option<String>,
+    /// The files the schedule was parsed from, with their content hashes.
+    #[serde(rename = "Джерела", default, skip_serializing_if = "Vec::is_empty")]
+    pub sources: Vec<SourceFile>,
+}
+
+impl Metadata {
+    /// Detects the metadata mentioned in file names and title rows.
+    ///
+    /// # Arguments
+    ///
+    /// * `texts`: File names and title rows (e.g. `Розклад занять, 2024-2025 н.р., 3 курс (бакалаврат)`).
+    ///
+    /// # Returns
+    ///
+    /// The metadata found in the texts, with the first mention of every field.
+    pub fn detect<S: AsRef<str>>(texts: &[S]) -> Self {
+        let mut metadata = Metadata::default();
+
+        for text in texts {
+            let text = text.as_ref().to_lowercase();
+
+            metadata.academic_year = metadata.academic_year.or_else(|| AcademicYear::find(&text));
+            metadata.term = metadata.term.or_else(|| Term::find(&text));
+            metadata.course = metadata.course.or_else(|| Self::find_course(&text));
+            metadata.level = metadata.level.or_else(|| Level::find(&text));
+        }
+
+        metadata
+    }
+
+    /// Fills the fields missing from this metadata with the fields of another one,
+    /// appending its sources.
+    ///
+    /// # Arguments
+    ///
+    /// * `other`: The metadata of lower precedence (e.g. detected from file names).
+    ///
+    /// # Returns
+    ///
+    /// The combined metadata.
+    pub fn or(mut self, other: Metadata) -> Self {
+        self.academic_year = self.academic_year.or(other.academic_year);
+        self.term = self.term.or(other.term);
+        self.course = self.course.or(other.course);
+        self.level = self.level.or(other.level);
+        self.generated_at = self.generated_at.or(other.generated_at);
+
+        for source in other.sources {
+            if !self.sources.contains(&source) {
+                self.sources.push(source);
+            }
+        }
+
+        self
+    }
+
+    /// Sets the generation time to `SOURCE_DATE_EPOCH` if the variable is set, or to the current time
+    /// if requested. Otherwise the time is cleared (e.g. the one of a parsed `schedule.json`),
+    /// so the output depends on the input files only.
+    ///
+    /// # Arguments
+    ///
+    /// * `now`: Whether the current time is set if `SOURCE_DATE_EPOCH` is not.
+    pub fn stamp(&mut self, now: bool) {
+        let time = match source_date_epoch() {
+            Some(time) => Some(time),
+            None if now => Some(chrono::Utc::now()),
+            None => None,
+        };
+
+        self.generated_at = time.map(|time| time.format("%Y-%m-%dT%H:%M:%SZ").to_string());
+    }
+
+    /// Checks whether no field is set.
+    pub fn is_empty(&self) -> bool {
+        *self == Metadata::default()
+    }
+
+    /// Finds the year of study (e.g. `3 курс`, `курс 3`).
+    fn find_course(text: &str) -> Option<u8> {
+        let words: Vec<&str> = text
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .collect();
+
+        words.windows(2).find_map(|pair| match pair {
+            [number, "курс"] | ["курс", number] => number.parse().ok().filter(|course| (1..=6).contains(course)),
+            _ => None,
+        })
+    }
+}
+
+/// Returns the time set with the `SOURCE_DATE_EPOCH` variable for reproducible builds, if any.
+pub fn source_date_epoch() -> Option<chrono::DateTime<chrono::Utc>> {
+    std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.trim().parse().ok())
+        .and_then(|seconds| chrono::DateTime::from_timestamp(seconds, 0))
+}
+
+/// Represents a file a schedule was parsed from.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SourceFile {
+    /// The file name.
+    #[serde(rename = "Файл")]
+    pub name: String,
+    /// The 64-bit FNV-1a hash of the file content.
+    #[serde(rename = "Хеш")]
+    pub hash: String,
+}
+
+impl SourceFile {
+    /// Hashes the content of a file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path`: The path to the file.
+    ///
+    /// # Returns
+    ///
+    /// A `ScheduleResult` containing the file name with its hash.
+    pub fn of(path: &Path) -> ScheduleResult<Self> {
+        let content = std::fs::read(path)?;
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| ScheduleError::InvalidPath(path.display().to_string()))?;
+
+        Ok(SourceFile {
+            name: name.to_owned(),
+            hash: format!("{:016x}", fnv1a(&content)),
+        })
+    }
+}
+
+/// Represents an academic year, starting in autumn.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AcademicYear {
+    /// The calendar year the academic year starts in.
+    pub start: u16,
+}
+
+impl AcademicYear {
+    /// Finds an academic year written as two consecutive years (e.g. `2024-2025`, `2024/25`).
+    fn find(text: &str) -> Option<Self> {
+        let numbers: Vec<&str> = text
+            .split(|c: char| !c.is_ascii_digit())
+            .filter(|number| !number.is_empty())
+            .collect();
+
+        numbers.windows(2).find_map(|pair| Self::from_parts(pair[0], pair[1]))
+    }
+
+    /// Creates an academic year from its first and second year, checking they are consecutive.
+    fn from_parts(first: &str, second: &str) -> Option<Self> {
+        let start: u16 = first.parse().ok().filter(|_| first.len() == 4)?;
+        let end: u16 = match second.len() {
+            4 => second.parse().ok()?,
+            2 => start / 100 * 100 + second.parse::<u16>().ok()?,
+            _ => return None,
+        };
+
+        (end % 100 == (start + 1) % 100).then_some(AcademicYear { start })
+    }
+}
+
+impl Display for AcademicYear {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.start, self.start + 1)
+    }
+}
+
+impl FromStr for AcademicYear {
+    type Err = ScheduleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.trim()
+            .split_once(['-', '/'])
+            .and_then(|(first, second)| Self::from_parts(first.trim(), second.trim()))
+            .ok_or_else(|| ScheduleError::InvalidMetadata(s.to_owned()))
+    }
+}
+
+/// Represents a trimester of the academic year.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Term {
+    /// The autumn trimester (`осінній`).
+    Autumn,
+    /// The spring trimester (`весняний`).
+    Spring,
+    /// The summer trimester (`літній`).
+    Summer,
+}
+
+impl Term {
+    /// Word stems (in lowercase) identifying trimesters in texts.
+    const STEMS: [(&'static str, Term); 6] = [
+        ("осін", Term::Autumn),
+        ("autumn", Term::Autumn),
+        ("весн", Term::Spring),
+        ("spring", Term::Spring),
+        ("літн", Term::Summer),
+        ("summer", Term::Summer),
+    ];
+
+    /// Finds the trimester mentioned in a lowercase text.
+    fn find(text: &str) -> Option<Self> {
+        Self::STEMS.iter().find(|(stem, _)| text.contains(stem)).map(|(_, term)| *term)
+    }
+}
+
+impl Display for Term {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let stringed = match self {
+            Term::Autumn => "осінній",
+            Term::Spring => "весняний",
+            Term::Summer => "літній",
+        };
+
+        write!(f, "{stringed}")
+    }
+}
+
+impl FromStr for Term {
+    type Err = ScheduleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::find(&s.trim().to_lowercase()).ok_or_else(|| ScheduleError::InvalidMetadata(s.to_owned()))
+    }
+}
+
+/// Represents the level of study.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    /// Bachelor studies (`бакалаврат`).
+    Bachelor,
+    /// Master studies (`магістратура`).
+    Master,
+}
+
+impl Level {
+    /// Word stems (in lowercase) identifying levels in texts.
+    const STEMS: [(&'static str, Level); 4] = [
+        ("бакалавр", Level::Bachelor),
+        ("bachelor", Level::Bachelor),
+        ("магістр", Level::Master),
+        ("master", Level::Master),
+    ];
+
+    /// Finds the level mentioned in a lowercase text.
+    fn find(text: &str) -> Option<Self> {
+        Self::STEMS.iter().find(|(stem, _)| text.contains(stem)).map(|(_, level)| *level)
+    }
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let stringed = match self {
+            Level::Bachelor => "бакалаврат",
+            Level::Master => "магістратура",
+        };
+
+        write!(f, "{stringed}")
+    }
+}
+
+impl FromStr for Level {
+    type Err = ScheduleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::find(&s.trim().to_lowercase()).ok_or_else(|| ScheduleError::InvalidMetadata(s.to_owned()))
+    }
+}
+
+impl_serde_display_fromstr!(AcademicYear, Term, Level);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Formats the time expected from `SOURCE_DATE_EPOCH`, if set in the environment.
+    fn epoch() -> Option<String> {
+        source_date_epoch().map(|time| time.format("%Y-%m-%dT%H:%M:%SZ").to_string())
+    }
+
+    #[test]
+    fn clears_the_generation_time_unless_requested() {
+        let mut metadata = Metadata { generated_at: Some("2020-01-01T00:00:00Z".to_owned()), ..Default::default() };
+        metadata.stamp(false);
+
+        assert_eq!(metadata.generated_at, epoch());
+    }
+
+    #[test]
+    fn sets_the_generation_time_on_request() {
+        let mut metadata = Metadata { generated_at: Some("2020-01-01T00:00:00Z".to_owned()), ..Default::default() };
+        metadata.stamp(true);
+
+        let time = metadata.generated_at.unwrap();
+        assert!(epoch().map_or(time != "2020-01-01T00:00:00Z", |epoch| epoch == time));
+    }
+}
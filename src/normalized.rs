@@ -0,0 +1,219 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    exam::ExamSession,
+    group::Group,
+    metadata::Metadata,
+    schedule::*,
+};
+
+/// Represents a university schedule where every lesson is stored once
+/// and referenced by the specialities attending it.
+///
+/// The nested [`Schedule`] is the denormalized view of it, where a lecture shared
+/// by several specialities (e.g. `(екон.+фін.)`) is copied into each of them.
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+pub struct NormalizedSchedule {
+    /// The academic year, term, course and level of the schedule, with its sources.
+    #[serde(rename = "Метадані", default, skip_serializing_if = "Metadata::is_empty")]
+    pub metadata: Metadata,
+    /// University faculties with their lessons.
+    #[serde(rename = "Факультети")]
+    pub faculties: Vec<NormalizedFaculty>,
+    /// Exam/credit sessions of faculties.
+    #[serde(rename = "Сесії", default, skip_serializing_if = "Vec::is_empty")]
+    pub exam_sessions: Vec<ExamSession>,
+}
+
+impl NormalizedSchedule {
+    /// Normalizes a schedule, merging the copies of shared lessons.
+    pub fn of(schedule: &Schedule) -> Self {
+        NormalizedSchedule {
+            metadata: schedule.metadata.clone(),
+            faculties: schedule.faculties.iter().map(NormalizedFaculty::of).collect(),
+            exam_sessions: schedule.exam_sessions.clone(),
+        }
+    }
+
+    /// Converts the schedule into the denormalized view, copying shared lessons into every speciality.
+    pub fn to_schedule(&self) -> Schedule {
+        Schedule {
+            metadata: self.metadata.clone(),
+            faculties: self.faculties.iter().map(NormalizedFaculty::to_faculty).collect(),
+            exam_sessions: self.exam_sessions.clone(),
+        }
+    }
+}
+
+/// Represents a university faculty with its lessons stored once by identifier.
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+pub struct NormalizedFaculty {
+    /// The identifier of the faculty, a slug of its name.
+    #[serde(rename = "Ідентифікатор")]
+    pub id: String,
+    /// The name of the faculty.
+    #[serde(rename = "Назва факультету")]
+    pub name: String,
+    /// Every lesson of the faculty by its identifier.
+    #[serde(rename = "Заняття")]
+    pub lessons: BTreeMap<String, SharedLesson>,
+    /// Identifiers of the lessons attended by every speciality.
+    #[serde(rename = "Спеціальності")]
+    pub specialities: BTreeMap<SpecialityName, Vec<String>>,
+}
+
+impl NormalizedFaculty {
+    /// Normalizes a faculty: lessons with equal identifiers in several specialities
+    /// are stored once, with the list of the specialities attending them.
+    pub fn of(faculty: &Faculty) -> Self {
+        let mut normalized = NormalizedFaculty {
+            id: faculty.id.clone(),
+            name: faculty.name.clone(),
+            ..Default::default()
+        };
+
+        for (name, speciality) in &faculty.specialities {
+            let references = normalized.specialities.entry(*name).or_default();
+
+            for (discipline, lessons) in &speciality.disciplines {
+                for group in &lessons.groups {
+                    references.push(group.id.clone());
+
+                    let shared = normalized.lessons
+                        .entry(group.id.clone())
+                        .or_insert_with(|| SharedLesson {
+                            discipline: discipline.clone(),
+                            group: group.clone(),
+                            specialities: vec![],
+                        });
+
+                    if !shared.specialities.contains(name) {
+                        shared.specialities.push(*name);
+                    }
+                }
+            }
+        }
+
+        normalized
+    }
+
+    /// Converts the faculty into the denormalized view, copying shared lessons into every speciality.
+    pub fn to_faculty(&self) -> Faculty {
+        let mut specialities = Specialities::new();
+
+        for (name, references) in &self.specialities {
+            let speciality = specialities
+                .entry(*name)
+                .or_insert_with(|| Speciality { id: String::new(), disciplines: Disciplines::new() });
+
+            for lesson in references.iter().filter_map(|id| self.lessons.get(id)) {
+                speciality.disciplines
+                    .entry(lesson.discipline.clone())
+                    .or_insert_with(|| Discipline { id: String::new(), groups: vec![] })
+                    .groups
+                    .push(lesson.group.clone());
+            }
+
+            for discipline in speciality.disciplines.values_mut() {
+                discipline.sort_groups();
+            }
+        }
+
+        let mut faculty = Faculty {
+            id: String::new(),
+            name: self.name.clone(),
+            specialities,
+            skipped_rows: vec![],
+            merges: vec![],
+        };
+        faculty.assign_ids();
+
+        faculty
+    }
+
+    /// Returns the lessons attended by several specialities together (e.g. common lectures).
+    pub fn shared(&self) -> impl Iterator<Item = &SharedLesson> {
+        self.lessons.values().filter(|lesson| lesson.specialities.len() > 1)
+    }
+}
+
+/// Represents a lesson stored once, with the specialities attending it.
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+pub struct SharedLesson {
+    /// The full discipline name, as written in the spreadsheet.
+    #[serde(rename = "Дисципліна")]
+    pub discipline: String,
+    /// The lesson group with its identifier, time, weeks and auditorium.
+    #[serde(flatten)]
+    pub group: Group,
+    /// The specialities attending the lesson.
+    #[serde(rename = "Спеціальності")]
+    pub specialities: Vec<SpecialityName>,
+}
+
+impl Display for SharedLesson {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let specialities: Vec<String> = self.specialities.iter().map(ToString::to_string).collect();
+
+        write!(
+            f,
+            "{} {} {} — {}: {}",
+            self.group.day,
+            self.group.time,
+            Discipline::short_name(&self.discipline),
+            self.group.name,
+            specialities.join(", "),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    /// Parses a schedule with a lecture shared by the Економіка and Фінанси specialities.
+    fn schedule() -> Schedule {
+        let csv = "День;Час;Дисципліна;Група;Тижні;Аудиторія\n\
+            Понеділок;08:30-09:50;Мікроекономіка (екон.+фін.);лекція;1-13;1-225\n\
+            Понеділок;10:00-11:20;Мікроекономіка (екон.+фін.);1;1-13;3-205\n\
+            Вівторок;08:30-09:50;Статистика (екон.);1;1-13;3-205\n";
+        let meta = FacultyMeta { name: "ФЕН".to_owned(), speciality: None };
+
+        Schedule::from_sources([(Cursor::new(csv), meta)]).unwrap()
+    }
+
+    #[test]
+    fn stores_shared_lessons_once() {
+        let schedule = schedule();
+        let faculty = NormalizedFaculty::of(&schedule.faculties[0]);
+        let mut shared: Vec<String> = faculty.shared().map(ToString::to_string).collect();
+        shared.sort();
+
+        assert_eq!(faculty.lessons.len(), 3);
+        assert_eq!(faculty.specialities[&SpecialityName::Economics].len(), 3);
+        assert_eq!(faculty.specialities[&SpecialityName::Finances].len(), 2);
+        assert_eq!(shared, [
+            "Понеділок 08:30-09:50 Мікроекономіка — лекція: Економіка, Фінанси",
+            "Понеділок 10:00-11:20 Мікроекономіка — 1: Економіка, Фінанси",
+        ]);
+    }
+
+    #[test]
+    fn normalized_json_is_read_back_as_the_same_schedule() {
+        let schedule = schedule();
+        let json = serde_json::to_string(&NormalizedSchedule::of(&schedule)).unwrap();
+        let normalized: NormalizedSchedule = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(
+            serde_json::to_string(&normalized.to_schedule()).unwrap(),
+            serde_json::to_string(&schedule).unwrap(),
+        );
+    }
+}
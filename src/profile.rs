@@ -0,0 +1,384 @@
+use std::{
+    fmt::Display,
+    path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::read_document,
+    exam::Date,
+    group::GroupNumber,
+    schedule::*,
+    timetable::{DisciplineChoice, Selection},
+    error::{ScheduleError, ScheduleResult},
+};
+
+/// Represents a student profile describing personal enrolments, read from a TOML or JSON file.
+///
+/// ```toml
+/// faculty = "Факультет Інформатики"
+/// speciality = "Інженерія програмного забезпечення"
+/// semester_start = "02.09.2024"
+///
+/// [[disciplines]]
+/// name = "Системне програмування"
+/// groups = [2]
+/// ```
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+pub struct Profile {
+    /// The name of the student's faculty.
+    pub faculty: Option<String>,
+    /// The student's speciality.
+    pub speciality: Option<SpecialityName>,
+    /// The first day of the semester (any day of the first studying week).
+    pub semester_start: Option<Date>,
+    /// Disciplines the student is enrolled in.
+    #[serde(default)]
+    pub disciplines: Vec<Enrolment>,
+}
+
+/// Represents an enrolment of a student in a discipline.
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+pub struct Enrolment {
+    /// A part of the discipline name to search for.
+    pub name: String,
+    /// Chosen group numbers. If empty, every group of the discipline is chosen.
+    #[serde(default)]
+    pub groups: Vec<GroupNumber>,
+}
+
+impl Profile {
+    /// Reads a student profile from a file.
+    ///
+    /// Files with the `.json` extension are read as JSON, any others as TOML.
+    ///
+    /// # Arguments
+    ///
+    /// * `path`: The path to the profile file.
+    ///
+    /// # Returns
+    ///
+    /// A `ScheduleResult` containing the read profile.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> ScheduleResult<Self> {
+        read_document(path.as_ref())
+    }
+
+    /// Converts the profile into a timetable selection.
+    pub fn selection(&self) -> Selection {
+        Selection {
+            faculty: self.faculty.clone(),
+            speciality: self.speciality,
+            week: None,
+            disciplines: self.disciplines
+                .iter()
+                .map(|enrolment| DisciplineChoice {
+                    discipline: enrolment.name.clone(),
+                    groups: enrolment.groups.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Checks the profile against the schedule.
+    ///
+    /// # Arguments
+    ///
+    /// * `schedule`: The parsed schedule.
+    ///
+    /// # Returns
+    ///
+    /// Every mismatch between the profile and the schedule (e.g. renamed disciplines or removed groups).
+    pub fn issues(&self, schedule: &Schedule) -> Vec<ProfileIssue> {
+        let mut issues = vec![];
+
+        if let Some(faculty) = &self.faculty {
+            if !schedule.faculties.iter().any(|f| &f.name == faculty) {
+                let names = schedule.faculties.iter().map(|f| f.name.as_str());
+
+                issues.push(ProfileIssue::UnknownFaculty {
+                    name: faculty.clone(),
+                    suggestion: closest(faculty, names),
+                });
+
+                return issues;
+            }
+        }
+
+        if let Some(speciality) = self.speciality {
+            let found = self.faculties(schedule).any(|f| f.specialities.contains_key(&speciality));
+
+            if !found {
+                issues.push(ProfileIssue::UnknownSpeciality(speciality));
+                return issues;
+            }
+        }
+
+        for enrolment in &self.disciplines {
+            let disciplines: Vec<(&String, &Discipline)> = self
+                .disciplines_of(schedule)
+                .filter(|(name, _)| name.contains(&enrolment.name))
+                .collect();
+
+            let in_exams = schedule.exam_sessions
+                .iter()
+                .flat_map(|session| &session.exams)
+                .any(|exam| exam.discipline.contains(&enrolment.name));
+
+            if disciplines.is_empty() {
+                if !in_exams {
+                    let names: Vec<&str> = self
+                        .disciplines_of(schedule)
+                        .map(|(name, _)| Discipline::short_name(name))
+                        .collect();
+
+                    issues.push(ProfileIssue::UnknownDiscipline {
+                        name: enrolment.name.clone(),
+                        suggestion: closest(&enrolment.name, names),
+                    });
+                }
+
+                continue;
+            }
+
+            let mut available: Vec<GroupNumber> = disciplines
+                .iter()
+                .flat_map(|(_, discipline)| &discipline.groups)
+                .filter(|group| !group.name.is_for_each_group())
+                .flat_map(|group| group.name.groups.iter().copied())
+                .collect();
+            available.sort_unstable();
+            available.dedup();
+
+            for group in &enrolment.groups {
+                if !available.contains(group) {
+                    issues.push(ProfileIssue::MissingGroup {
+                        discipline: enrolment.name.clone(),
+                        group: *group,
+                        available: available.clone(),
+                    });
+                }
+            }
+        }
+
+        issues
+    }
+
+    /// Validates the profile against the schedule.
+    ///
+    /// # Returns
+    ///
+    /// An error listing every mismatch if the profile does not fit the schedule.
+    pub fn validate(&self, schedule: &Schedule) -> ScheduleResult<()> {
+        let issues = self.issues(schedule);
+
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            let lines: Vec<String> = issues.iter().map(|issue| format!("- {issue}")).collect();
+            Err(ScheduleError::InvalidProfile(lines.join("\n")))
+        }
+    }
+
+    /// Returns the faculties of the schedule fitting the profile.
+    fn faculties<'a>(&'a self, schedule: &'a Schedule) -> impl Iterator<Item = &'a Faculty> {
+        schedule.faculties
+            .iter()
+            .filter(|f| self.faculty.as_ref().is_none_or(|name| &f.name == name))
+    }
+
+    /// Returns the disciplines of the schedule fitting the profile's faculty and speciality.
+    fn disciplines_of<'a>(&'a self, schedule: &'a Schedule) -> impl Iterator<Item = (&'a String, &'a Discipline)> {
+        self.faculties(schedule)
+            .flat_map(|f| &f.specialities)
+            .filter(|(speciality, _)| self.speciality.is_none_or(|s| **speciality == s))
+            .flat_map(|(_, speciality)| &speciality.disciplines)
+    }
+}
+
+/// Represents a mismatch between a student profile and the schedule.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ProfileIssue {
+    /// The faculty is missing from the schedule.
+    UnknownFaculty {
+        /// The faculty name from the profile.
+        name: String,
+        /// The most similar faculty name of the schedule.
+        suggestion: Option<String>,
+    },
+    /// The speciality is missing from the faculty.
+    UnknownSpeciality(SpecialityName),
+    /// No discipline contains the name from the profile (e.g. it was renamed).
+    UnknownDiscipline {
+        /// The discipline name from the profile.
+        name: String,
+        /// The most similar discipline name of the schedule.
+        suggestion: Option<String>,
+    },
+    /// The discipline has no such group (e.g. groups were merged).
+    MissingGroup {
+        /// The discipline name from the profile.
+        discipline: String,
+        /// The missing group number.
+        group: GroupNumber,
+        /// Group numbers of the discipline found in the schedule.
+        available: Vec<GroupNumber>,
+    },
+}
+
+impl Display for ProfileIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProfileIssue::UnknownFaculty { name, suggestion } => {
+                write!(f, "Faculty `{name}` is not in the schedule")?;
+                if let Some(suggestion) = suggestion {
+                    write!(f, "; did you mean `{suggestion}`?")?;
+                }
+                Ok(())
+            },
+            ProfileIssue::UnknownSpeciality(speciality) => {
+                write!(f, "Speciality `{speciality}` is not in the schedule")
+            },
+            ProfileIssue::UnknownDiscipline { name, suggestion } => {
+                write!(f, "Discipline `{name}` is not in the schedule (renamed or removed?)")?;
+                if let Some(suggestion) = suggestion {
+                    write!(f, "; did you mean `{suggestion}`?")?;
+                }
+                Ok(())
+            },
+            ProfileIssue::MissingGroup { discipline, group, available } => {
+                let available: Vec<String> = available.iter().map(u8::to_string).collect();
+                write!(
+                    f, "Discipline `{discipline}` has no group {group}; available groups: {}",
+                    if available.is_empty() { "none".to_owned() } else { available.join(", ") }
+                )
+            },
+        }
+    }
+}
+
+/// Finds the most similar name by the Dice coefficient of character bigrams,
+/// ignoring names which are too different.
+fn closest<'a, I: IntoIterator<Item = &'a str>>(name: &str, names: I) -> Option<String> {
+    /// The minimal similarity of a suggested name.
+    const THRESHOLD: f64 = 0.4;
+
+    let bigrams = |s: &str| -> Vec<(char, char)> {
+        let chars: Vec<char> = s.to_lowercase().chars().filter(|c| !c.is_whitespace()).collect();
+        chars.windows(2).map(|pair| (pair[0], pair[1])).collect()
+    };
+
+    let expected = bigrams(name);
+
+    names
+        .into_iter()
+        .map(|candidate| {
+            let mut actual = bigrams(candidate);
+            let total = expected.len() + actual.len();
+            let mut common = 0;
+
+            for bigram in &expected {
+                if let Some(position) = actual.iter().position(|b| b == bigram) {
+                    actual.swap_remove(position);
+                    common += 1;
+                }
+            }
+
+            let similarity = if total == 0 { 0.0 } else { 2.0 * common as f64 / total as f64 };
+            (similarity, candidate)
+        })
+        .filter(|(similarity, _)| *similarity >= THRESHOLD)
+        .max_by(|(a, _), (b, _)| a.total_cmp(b))
+        .map(|(_, candidate)| candidate.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    /// Parses a schedule of the Економіка speciality of `Факультет економічних наук`.
+    fn schedule() -> Schedule {
+        let csv = "День;Час;Дисципліна;Група;Тижні;Аудиторія\n\
+            Понеділок;08:30-09:50;Мікроекономіка;лекція;1-13;1-225\n\
+            Понеділок;10:00-11:20;Мікроекономіка;1;1-13;1-225\n\
+            Понеділок;11:40-13:00;Мікроекономіка;2;1-13;1-225\n\
+            Вівторок;08:30-09:50;Статистика;1;1-13;3-205\n";
+        let meta = FacultyMeta { name: "Факультет економічних наук".to_owned(), speciality: Some(SpecialityName::Economics) };
+
+        Schedule::from_sources([(Cursor::new(csv), meta)]).unwrap()
+    }
+
+    /// Creates a profile of the faculty enrolled in the discipline groups.
+    fn profile(faculty: &str, speciality: SpecialityName, disciplines: &[(&str, &[GroupNumber])]) -> Profile {
+        Profile {
+            faculty: Some(faculty.to_owned()),
+            speciality: Some(speciality),
+            semester_start: None,
+            disciplines: disciplines
+                .iter()
+                .map(|(name, groups)| Enrolment { name: name.to_string(), groups: groups.to_vec() })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn accepts_profiles_fitting_the_schedule() {
+        let profile = profile(
+            "Факультет економічних наук",
+            SpecialityName::Economics,
+            &[("Мікроекономіка", &[2]), ("Статистика", &[])],
+        );
+
+        assert!(profile.issues(&schedule()).is_empty());
+        assert!(profile.validate(&schedule()).is_ok());
+    }
+
+    #[test]
+    fn suggests_similar_faculty_names() {
+        let profile = profile("Факультет економічних наук.", SpecialityName::Economics, &[]);
+
+        assert_eq!(profile.issues(&schedule()), [ProfileIssue::UnknownFaculty {
+            name: "Факультет економічних наук.".to_owned(),
+            suggestion: Some("Факультет економічних наук".to_owned()),
+        }]);
+    }
+
+    #[test]
+    fn reports_missing_specialities() {
+        let profile = profile("Факультет економічних наук", SpecialityName::SoftwareEngineering, &[("Мікроекономіка", &[1])]);
+
+        assert_eq!(profile.issues(&schedule()), [ProfileIssue::UnknownSpeciality(SpecialityName::SoftwareEngineering)]);
+    }
+
+    #[test]
+    fn suggests_similar_discipline_names() {
+        let profile = profile(
+            "Факультет економічних наук",
+            SpecialityName::Economics,
+            &[("Мікроекономіка (2)", &[]), ("Квантова фізика", &[])],
+        );
+
+        assert_eq!(profile.issues(&schedule()), [
+            ProfileIssue::UnknownDiscipline {
+                name: "Мікроекономіка (2)".to_owned(),
+                suggestion: Some("Мікроекономіка".to_owned()),
+            },
+            ProfileIssue::UnknownDiscipline { name: "Квантова фізика".to_owned(), suggestion: None },
+        ]);
+    }
+
+    #[test]
+    fn lists_available_groups_of_missing_ones() {
+        let profile = profile("Факультет економічних наук", SpecialityName::Economics, &[("Мікроекономіка", &[3])]);
+        let error = profile.validate(&schedule()).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            ScheduleError::InvalidProfile(
+                "- Discipline `Мікроекономіка` has no group 3; available groups: 1, 2".to_owned()
+            ).to_string(),
+        );
+    }
+}
@@ -0,0 +1,24 @@
+/// Static HTML timetable renderer.
+pub mod html;
+/// Compact Markdown and plain-text timetable renderer for chats.
+pub mod text;
+/// iCalendar renderer for calendar applications.
+pub mod ics;
+
+/// Escapes the characters having a special meaning in HTML.
+pub fn escape_html(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
@@ -0,0 +1,193 @@
+use std::fmt::Write;
+
+use crate::{
+    group::Day,
+    timetable::{Timetable, Lesson, ExamEntry},
+};
+use super::escape_html;
+
+/// Inline style sheet of the rendered page, so no external assets are required.
+const STYLE: &str = "\
+body { font-family: sans-serif; margin: 1em; }
+table { border-collapse: collapse; width: 100%; table-layout: fixed; }
+th, td { border: 1px solid #999; padding: 4px; vertical-align: top; }
+th { background: #eee; }
+.lesson { margin-bottom: 4px; padding: 2px; border-left: 3px solid #369; background: #f4f8fc; }
+.lesson .discipline { font-weight: bold; }
+.lesson .details { font-size: smaller; color: #444; }
+.hidden { display: none; }
+";
+
+/// Inline script hiding lessons which are not taught in the selected week.
+const SCRIPT: &str = "\
+document.getElementById('week').addEventListener('change', function (e) {
+    var week = e.target.value;
+    document.querySelectorAll('.lesson').forEach(function (lesson) {
+        var weeks = lesson.getAttribute('data-weeks').split(' ');
+        lesson.classList.toggle('hidden', week !== '' && weeks.indexOf(week) < 0);
+    });
+});
+";
+
+/// Renders a timetable as a standalone HTML page with a weekly grid.
+///
+/// Days are placed in columns and lesson times in rows. A week selector
+/// hides the lessons which are not taught during the chosen week.
+///
+/// # Arguments
+///
+/// * `timetable`: The timetable to render.
+/// * `title`: The title of the page (e.g. speciality name).
+///
+/// # Returns
+///
+/// The HTML document as a string.
+pub fn render(timetable: &Timetable, title: &str) -> String {
+    let mut html = String::new();
+    let title = escape_html(title);
+
+    let _ = writeln!(html, "<!DOCTYPE html>");
+    let _ = writeln!(html, "<html lang=\"uk\">\n<head>\n<meta charset=\"utf-8\">");
+    let _ = writeln!(html, "<title>{title}</title>\n<style>\n{STYLE}</style>\n</head>\n<body>");
+    let _ = writeln!(html, "<h1>{title}</h1>");
+
+    let _ = writeln!(html, "<label for=\"week\">Тиждень: </label>");
+    let _ = writeln!(html, "<select id=\"week\">\n<option value=\"\">Усі тижні</option>");
+    for week in timetable.weeks() {
+        let _ = writeln!(html, "<option value=\"{week}\">{week}</option>");
+    }
+    let _ = writeln!(html, "</select>");
+
+    let _ = writeln!(html, "<table>\n<thead>\n<tr><th>Час</th>");
+    for day in Day::ALL {
+        let _ = writeln!(html, "<th>{day}</th>");
+    }
+    let _ = writeln!(html, "</tr>\n</thead>\n<tbody>");
+
+    for time in timetable.slots() {
+        let _ = writeln!(html, "<tr>\n<th>{time}</th>");
+
+        for day in Day::ALL {
+            let _ = write!(html, "<td>");
+            for lesson in timetable.at(day, time) {
+                render_lesson(&mut html, lesson);
+            }
+            let _ = writeln!(html, "</td>");
+        }
+
+        let _ = writeln!(html, "</tr>");
+    }
+
+    let _ = writeln!(html, "</tbody>\n</table>");
+    let _ = writeln!(html, "<script>\n{SCRIPT}</script>\n</body>\n</html>");
+
+    html
+}
+
+/// Writes a single lesson cell entry.
+fn render_lesson(html: &mut String, lesson: &Lesson) {
+    let weeks: Vec<String> = lesson.group.weeks
+        .numbers()
+        .iter()
+        .map(u8::to_string)
+        .collect();
+
+    let _ = write!(
+        html,
+        "<div class=\"lesson\" data-weeks=\"{}\">\
+        <div class=\"discipline\">{}</div>\
+        <div class=\"details\">{} &middot; {} &middot; тижні {}</div>\
+        </div>",
+        weeks.join(" "),
+        escape_html(lesson.discipline),
+        escape_html(&lesson.group.name.to_string()),
+        escape_html(&lesson.group.auditorium.to_string()),
+        escape_html(&lesson.group.weeks.to_string()),
+    );
+}
+
+/// Renders exams as a standalone HTML page with a table sorted by date and time.
+///
+/// # Arguments
+///
+/// * `exams`: The exams to render.
+/// * `title`: The title of the page (e.g. speciality name).
+///
+/// # Returns
+///
+/// The HTML document as a string.
+pub fn render_exams(exams: &[ExamEntry], title: &str) -> String {
+    let mut html = String::new();
+    let title = escape_html(title);
+
+    let _ = writeln!(html, "<!DOCTYPE html>");
+    let _ = writeln!(html, "<html lang=\"uk\">\n<head>\n<meta charset=\"utf-8\">");
+    let _ = writeln!(html, "<title>{title}</title>\n<style>\n{STYLE}</style>\n</head>\n<body>");
+    let _ = writeln!(html, "<h1>{title}</h1>");
+
+    let _ = writeln!(html, "<table>\n<thead>\n<tr>");
+    for header in ["Дата", "Час", "Дисципліна", "Група", "Аудиторія"] {
+        let _ = writeln!(html, "<th>{header}</th>");
+    }
+    let _ = writeln!(html, "</tr>\n</thead>\n<tbody>");
+
+    for ExamEntry { exam, .. } in exams {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            exam.date,
+            exam.time,
+            escape_html(&exam.discipline),
+            escape_html(&exam.group.to_string()),
+            escape_html(&exam.auditorium.to_string()),
+        );
+    }
+
+    let _ = writeln!(html, "</tbody>\n</table>\n</body>\n</html>");
+
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::{
+        schedule::{FacultyMeta, Schedule, SpecialityName},
+        timetable::Selection,
+    };
+
+    /// Parses a schedule of the Економіка speciality from CSV rows below the header.
+    fn schedule(rows: &str) -> Schedule {
+        let csv = format!("День;Час;Дисципліна;Група;Тижні;Аудиторія\n{rows}");
+        let meta = FacultyMeta { name: "Факультет".to_owned(), speciality: Some(SpecialityName::Economics) };
+
+        Schedule::from_sources([(Cursor::new(csv.into_bytes()), meta)]).unwrap()
+    }
+
+    #[test]
+    fn renders_lessons_in_the_cells_of_their_day_and_time() {
+        let schedule = schedule(
+            "Понеділок;08:30-09:50;Алгебра;лекція;1-3;1-225\n\
+             Середа;10:00-11:20;Економіка праці;1;2;3-205\n",
+        );
+        let html = render(&Timetable::new(&schedule, &Selection::default()), "Економіка");
+
+        assert!(html.starts_with("<!DOCTYPE html>\n"));
+        assert!(html.contains("<title>Економіка</title>"));
+        assert!(html.contains("<tr>\n<th>08:30-09:50</th>\n<td><div class=\"lesson\" data-weeks=\"1 2 3\">"));
+        assert!(html.contains("<tr>\n<th>10:00-11:20</th>\n<td></td>\n<td></td>\n<td><div class=\"lesson\" data-weeks=\"2\">"));
+        assert_eq!(html.matches("<option value=\"").count(), 4);
+    }
+
+    #[test]
+    fn escapes_titles_and_disciplines() {
+        let schedule = schedule("Понеділок;08:30-09:50;Алгебра <і> геометрія;лекція;1-13;1-225\n");
+        let html = render(&Timetable::new(&schedule, &Selection::default()), "Економіка & <бакалаври>");
+
+        assert!(html.contains("<h1>Економіка &amp; &lt;бакалаври&gt;</h1>"));
+        assert!(html.contains("<div class=\"discipline\">Алгебра &lt;і&gt; геометрія</div>"));
+        assert!(!html.contains("<і>"));
+    }
+}
@@ -0,0 +1,196 @@
+use crate::{
+    id::fnv1a,
+    group::Time,
+    exam::Date,
+    metadata::source_date_epoch,
+    timetable::{ExamEntry, Timetable},
+};
+
+/// The maximal length of a content line in octets, longer lines are folded.
+const LINE_OCTETS: usize = 75;
+
+/// Renders exams as an iCalendar (RFC 5545) document, which can be imported
+/// into calendar applications.
+///
+/// Event times are floating local times of the university, while creation times are in UTC.
+///
+/// # Arguments
+///
+/// * `exams`: The exams to render.
+/// * `name`: The name of the calendar (e.g. speciality name).
+///
+/// # Returns
+///
+/// The iCalendar document as a string.
+pub fn render_exams(exams: &[ExamEntry], name: &str) -> String {
+    let mut ics = String::new();
+    let stamp = stamp();
+
+    begin_calendar(&mut ics, name);
+
+    for ExamEntry { faculty, exam } in exams {
+        let uid = format!(
+            "{faculty}|{}|{}|{}|{}",
+            exam.date, exam.time, exam.discipline, exam.group.label()
+        );
+
+        push_line(&mut ics, "BEGIN:VEVENT");
+        push_line(&mut ics, &format!("UID:{:016x}@naukma_schedule", fnv1a(&uid)));
+        push_line(&mut ics, &format!("DTSTAMP:{stamp}"));
+        push_line(&mut ics, &format!("DTSTART:{}", date_time(exam.date, exam.time.from)));
+        if exam.time.has_end() {
+            push_line(&mut ics, &format!("DTEND:{}", date_time(exam.date, exam.time.to)));
+        }
+        push_line(&mut ics, &format!("SUMMARY:{}", escape_text(&exam.discipline)));
+        push_line(&mut ics, &format!("LOCATION:{}", escape_text(&exam.auditorium.to_string())));
+        push_line(&mut ics, &format!("DESCRIPTION:{}", escape_text(&format!("{}, {faculty}", exam.group))));
+        push_line(&mut ics, "END:VEVENT");
+    }
+
+    push_line(&mut ics, "END:VCALENDAR");
+
+    ics
+}
+
+/// Renders the lessons of a timetable as an iCalendar (RFC 5545) document,
+/// with an event for every lesson occurrence during the semester.
+///
+/// Event times are floating local times of the university, while creation times are in UTC.
+///
+/// # Arguments
+///
+/// * `timetable`: The timetable to render.
+/// * `semester_start`: Any day of the first studying week.
+/// * `name`: The name of the calendar (e.g. speciality name).
+///
+/// # Returns
+///
+/// The iCalendar document as a string.
+pub fn render_lessons(timetable: &Timetable, semester_start: Date, name: &str) -> String {
+    let mut ics = String::new();
+    let stamp = stamp();
+
+    begin_calendar(&mut ics, name);
+
+    for lesson in &timetable.lessons {
+        for week in lesson.group.weeks.numbers() {
+            let date = lesson.date(semester_start, week);
+            let uid = format!("{}|{date}", lesson.group.id);
+
+            push_line(&mut ics, "BEGIN:VEVENT");
+            push_line(&mut ics, &format!("UID:{:016x}@naukma_schedule", fnv1a(&uid)));
+            push_line(&mut ics, &format!("DTSTAMP:{stamp}"));
+            push_line(&mut ics, &format!("DTSTART:{}", date_time(date, lesson.group.time.from)));
+            push_line(&mut ics, &format!("DTEND:{}", date_time(date, lesson.group.time.to)));
+            push_line(&mut ics, &format!("SUMMARY:{}", escape_text(lesson.discipline)));
+            push_line(&mut ics, &format!("LOCATION:{}", escape_text(&lesson.group.auditorium.to_string())));
+            push_line(&mut ics, &format!("DESCRIPTION:{}", escape_text(&format!("{}, {}", lesson.group.name, lesson.faculty))));
+            push_line(&mut ics, "END:VEVENT");
+        }
+    }
+
+    push_line(&mut ics, "END:VCALENDAR");
+
+    ics
+}
+
+/// Writes the calendar properties, starting the document.
+fn begin_calendar(ics: &mut String, name: &str) {
+    push_line(ics, "BEGIN:VCALENDAR");
+    push_line(ics, "VERSION:2.0");
+    push_line(ics, "PRODID:-//naukma_schedule//UK");
+    push_line(ics, "CALSCALE:GREGORIAN");
+    push_line(ics, &format!("X-WR-CALNAME:{}", escape_text(name)));
+}
+
+/// Returns the creation time of the events in UTC (e.g. `20240115T083000Z`),
+/// taken from `SOURCE_DATE_EPOCH` if set, so reproducible builds get the same output.
+fn stamp() -> String {
+    source_date_epoch()
+        .unwrap_or_else(chrono::Utc::now)
+        .format("%Y%m%dT%H%M%SZ")
+        .to_string()
+}
+
+/// Appends a content line, folding it into lines of at most 75 octets
+/// (continuation lines start with a space), as RFC 5545 requires.
+fn push_line(ics: &mut String, line: &str) {
+    let mut length = 0;
+
+    for c in line.chars() {
+        if length + c.len_utf8() > LINE_OCTETS {
+            ics.push_str("\r\n ");
+            length = 1;
+        }
+
+        ics.push(c);
+        length += c.len_utf8();
+    }
+
+    ics.push_str("\r\n");
+}
+
+/// Formats a date and time in iCalendar local time format (e.g. `20240115T083000`).
+pub fn date_time(date: Date, time: Time) -> String {
+    format!(
+        "{:04}{:02}{:02}T{:02}{:02}00",
+        date.year(), date.month(), date.day(), time.hours(), time.minutes()
+    )
+}
+
+/// Escapes the characters having a special meaning in iCalendar text values.
+pub fn escape_text(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::{exam::Exam, group::{Auditorium, LessonTime, LessonType}, schedule::SpecialityName};
+
+    #[test]
+    fn folds_long_lines() {
+        let mut ics = String::new();
+        let summary = format!("SUMMARY:{}", "Інженерія програмного забезпечення ".repeat(3));
+        push_line(&mut ics, &summary);
+
+        let lines: Vec<&str> = ics.trim_end_matches("\r\n").split("\r\n").collect();
+
+        assert!(lines.len() > 1);
+        assert!(lines.iter().all(|line| line.len() <= LINE_OCTETS));
+        assert!(lines[1..].iter().all(|line| line.starts_with(' ')));
+        assert_eq!(lines.iter().enumerate().map(|(i, line)| if i == 0 { *line } else { &line[1..] }).collect::<String>(), summary);
+    }
+
+    #[test]
+    fn short_lines_are_not_folded() {
+        let mut ics = String::new();
+        push_line(&mut ics, "BEGIN:VEVENT");
+
+        assert_eq!(ics, "BEGIN:VEVENT\r\n");
+    }
+
+    #[test]
+    fn stamps_events_in_utc() {
+        let exam = Exam {
+            date: Date::from_str("15.01.2024").unwrap(),
+            time: LessonTime::from_str("10:00").unwrap(),
+            discipline: "Алгебра".to_owned(),
+            group: LessonType::from_str("1").unwrap(),
+            auditorium: Auditorium::from_str("1-225").unwrap(),
+            specialities: vec![SpecialityName::Economics],
+        };
+        let ics = render_exams(&[ExamEntry { faculty: "Факультет", exam: &exam }], "Економіка");
+
+        let stamp = ics.lines().find_map(|line| line.strip_prefix("DTSTAMP:")).unwrap();
+        assert_eq!(stamp.len(), "20240115T083000Z".len());
+        assert!(stamp.ends_with('Z'));
+        assert!(ics.contains("DTSTART:20240115T100000\r\n"));
+        assert!(!ics.contains("DTEND"));
+    }
+}
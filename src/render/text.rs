@@ -0,0 +1,183 @@
+use std::fmt::Write;
+use clap::ValueEnum;
+
+use crate::{
+    group::Day,
+    schedule::Discipline,
+    timetable::{Timetable, Lesson},
+};
+
+/// Represents the output format of the text renderer.
+#[derive(ValueEnum, Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextFormat {
+    /// Markdown list of lessons per day.
+    #[default]
+    Markdown,
+    /// Plain monospaced table per day.
+    Plain,
+}
+
+/// Renders a timetable as compact text, listing the lessons of each day sorted by time.
+///
+/// # Arguments
+///
+/// * `timetable`: The timetable to render.
+/// * `title`: The title of the timetable (e.g. speciality name).
+/// * `format`: The output text format.
+///
+/// # Returns
+///
+/// The rendered text.
+pub fn render(timetable: &Timetable, title: &str, format: TextFormat) -> String {
+    let mut text = String::new();
+
+    match format {
+        TextFormat::Markdown => {
+            let _ = writeln!(text, "## {title}");
+        },
+        TextFormat::Plain => {
+            let _ = writeln!(text, "{title}");
+        },
+    }
+
+    for day in Day::ALL {
+        let rows: Vec<[String; 4]> = timetable.lessons
+            .iter()
+            .filter(|l| l.group.day == day)
+            .map(row)
+            .collect();
+
+        if rows.is_empty() {
+            continue;
+        }
+
+        match format {
+            TextFormat::Markdown => render_markdown_day(&mut text, day, &rows),
+            TextFormat::Plain => render_plain_day(&mut text, day, &rows),
+        }
+    }
+
+    text
+}
+
+/// Column headers of the plain table.
+const HEADERS: [&str; 4] = ["Час", "Дисципліна", "Група", "Аудиторія"];
+
+/// Converts a lesson into text columns: time, short discipline name, group and room.
+fn row(lesson: &Lesson) -> [String; 4] {
+    [
+        lesson.group.time.to_string(),
+        Discipline::short_name(lesson.discipline).to_owned(),
+        lesson.group.name.to_string(),
+        lesson.group.auditorium.to_string(),
+    ]
+}
+
+/// Writes the lessons of a day as a Markdown list.
+fn render_markdown_day(text: &mut String, day: Day, rows: &[[String; 4]]) {
+    let _ = writeln!(text, "\n**{day}**");
+
+    for [time, discipline, group, auditorium] in rows {
+        let _ = writeln!(text, "- `{time}` {discipline} — {group}, {auditorium}");
+    }
+}
+
+/// Writes the lessons of a day as a monospaced table with aligned columns.
+fn render_plain_day(text: &mut String, day: Day, rows: &[[String; 4]]) {
+    let mut widths = HEADERS.map(|h| h.chars().count());
+
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let line = |cells: [&str; 4]| {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{cell}{}", " ".repeat(width - cell.chars().count())))
+            .collect();
+
+        padded.join(" | ").trim_end().to_owned()
+    };
+
+    let _ = writeln!(text, "\n{day}");
+    let _ = writeln!(text, "{}", line(HEADERS));
+    let _ = writeln!(text, "{}", widths.map(|w| "-".repeat(w)).join("-+-"));
+
+    for [time, discipline, group, auditorium] in rows {
+        let _ = writeln!(text, "{}", line([time, discipline, group, auditorium]));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::{
+        schedule::{FacultyMeta, Schedule, SpecialityName},
+        timetable::Selection,
+    };
+
+    /// Rows of lessons on two days, listed out of time order.
+    const ROWS: &str = "\
+        Середа;10:00-11:20;Економіка праці;1;2;3-205\n\
+        Понеділок;10:00-11:20;Алгебра;2;1-13;1-225\n\
+        Понеділок;08:30-09:50;Алгебра;лекція;1-13;КМЦ\n";
+
+    /// Parses a schedule of the Економіка speciality from CSV rows below the header.
+    fn schedule(rows: &str) -> Schedule {
+        let csv = format!("День;Час;Дисципліна;Група;Тижні;Аудиторія\n{rows}");
+        let meta = FacultyMeta { name: "Факультет".to_owned(), speciality: Some(SpecialityName::Economics) };
+
+        Schedule::from_sources([(Cursor::new(csv.into_bytes()), meta)]).unwrap()
+    }
+
+    #[test]
+    fn renders_markdown_lists_of_lessons_sorted_by_time() {
+        let schedule = schedule(ROWS);
+        let text = render(&Timetable::new(&schedule, &Selection::default()), "Економіка", TextFormat::Markdown);
+
+        assert_eq!(text, "\
+## Економіка
+
+**Понеділок**
+- `08:30-09:50` Алгебра — лекція, КМЦ
+- `10:00-11:20` Алгебра — 2, 1-225
+
+**Середа**
+- `10:00-11:20` Економіка праці — 1, 3-205
+");
+    }
+
+    #[test]
+    fn renders_plain_tables_with_aligned_columns() {
+        let schedule = schedule(ROWS);
+        let text = render(&Timetable::new(&schedule, &Selection::default()), "Економіка", TextFormat::Plain);
+
+        assert_eq!(text, "\
+Економіка
+
+Понеділок
+Час         | Дисципліна | Група  | Аудиторія
+------------+------------+--------+----------
+08:30-09:50 | Алгебра    | лекція | КМЦ
+10:00-11:20 | Алгебра    | 2      | 1-225
+
+Середа
+Час         | Дисципліна      | Група | Аудиторія
+------------+-----------------+-------+----------
+10:00-11:20 | Економіка праці | 1     | 3-205
+");
+    }
+
+    #[test]
+    fn skips_days_without_lessons() {
+        let schedule = schedule("");
+        let text = render(&Timetable::new(&schedule, &Selection::default()), "Економіка", TextFormat::Markdown);
+
+        assert_eq!(text, "## Економіка\n");
+    }
+}
@@ -0,0 +1,255 @@
+use std::fmt::Display;
+
+use calamine::Data;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    group::{Day, LessonTime, Weeks},
+    schedule::Schedule,
+};
+
+/// Represents the reason a worksheet row was not parsed into a lesson.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SkipReason {
+    /// Every cell of the row is empty (e.g. a separator between days).
+    Blank,
+    /// Only the day or time cells are filled, so the time slot has no lesson.
+    EmptySlot,
+    /// The lesson cells are filled, but the discipline cell is empty.
+    MissingDiscipline,
+    /// The lesson cells are filled, but the group cell is empty.
+    MissingGroup,
+    /// The lesson cells are filled, but the weeks cell is empty.
+    MissingWeeks,
+    /// The exam cells are filled, but no date is given in the row or above it.
+    MissingDate,
+}
+
+impl SkipReason {
+    /// Classifies a row of a weekly schedule worksheet, which misses the group or weeks cell.
+    ///
+    /// # Arguments
+    ///
+    /// * `row`: The cells of the row: day, time, discipline, group, weeks and auditorium.
+    ///
+    /// # Returns
+    ///
+    /// The reason of skipping the row, or `None` if the row is complete.
+    pub fn of_lesson_row(row: &[Data]) -> Option<Self> {
+        let is_empty = |index: usize| row.get(index).is_none_or(|cell| cell.to_string().trim().is_empty());
+
+        if (0..row.len()).all(is_empty) {
+            Some(SkipReason::Blank)
+        } else if (2..row.len()).all(is_empty) {
+            Some(SkipReason::EmptySlot)
+        } else if is_empty(2) {
+            Some(SkipReason::MissingDiscipline)
+        } else if is_empty(3) {
+            Some(SkipReason::MissingGroup)
+        } else if is_empty(4) {
+            Some(SkipReason::MissingWeeks)
+        } else {
+            None
+        }
+    }
+
+    /// Checks whether the row is partially filled, which is likely a data-entry mistake.
+    pub fn is_suspicious(&self) -> bool {
+        !matches!(self, SkipReason::Blank | SkipReason::EmptySlot)
+    }
+}
+
+impl Display for SkipReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SkipReason::Blank => write!(f, "blank row"),
+            SkipReason::EmptySlot => write!(f, "time slot without a lesson"),
+            SkipReason::MissingDiscipline => write!(f, "partially filled row without a discipline"),
+            SkipReason::MissingGroup => write!(f, "partially filled row without a group"),
+            SkipReason::MissingWeeks => write!(f, "partially filled row without weeks"),
+            SkipReason::MissingDate => write!(f, "exam without a date"),
+        }
+    }
+}
+
+/// Represents a worksheet row which was not parsed into a lesson.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SkippedRow {
+    /// The name of the worksheet.
+    pub sheet: String,
+    /// The row number, as shown by spreadsheet applications (starting from 1).
+    pub row: u32,
+    /// The reason of skipping the row.
+    pub reason: SkipReason,
+}
+
+impl Display for SkippedRow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "sheet `{}`, row {}: {}", self.sheet, self.row, self.reason)
+    }
+}
+
+/// Represents rows of a discipline merged into a single lesson group: identical rows,
+/// or rows differing only by weeks (e.g. `1-5` and `6-13`), which are coalesced.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct GroupMerge {
+    /// The full discipline name.
+    pub discipline: String,
+    /// The label of the lesson group.
+    pub lesson: String,
+    /// The day of the lesson.
+    pub day: Day,
+    /// The time of the lesson.
+    pub time: LessonTime,
+    /// The weeks of every merged row.
+    pub weeks: Vec<Weeks>,
+    /// The weeks of the resulting group.
+    pub merged: Weeks,
+}
+
+impl GroupMerge {
+    /// Checks whether the merged rows were identical.
+    pub fn is_identical(&self) -> bool {
+        self.weeks.iter().all(|weeks| weeks.numbers() == self.merged.numbers())
+    }
+}
+
+impl Display for GroupMerge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}` `{}` ({} {}): ", self.discipline.trim(), self.lesson, self.day, self.time)?;
+
+        if self.is_identical() {
+            write!(f, "merged {} identical rows", self.weeks.len())
+        } else {
+            let weeks: Vec<String> = self.weeks.iter().map(ToString::to_string).collect();
+            write!(f, "merged weeks {} into {}", weeks.join(" and "), self.merged)
+        }
+    }
+}
+
+/// Represents the report of parsing a schedule: the rows skipped in every faculty spreadsheet.
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+pub struct ParseReport {
+    /// Faculty (or exam session) names with their skipped rows.
+    pub skipped: Vec<(String, Vec<SkippedRow>)>,
+    /// Faculty names with their merged rows.
+    pub merged: Vec<(String, Vec<GroupMerge>)>,
+}
+
+impl ParseReport {
+    /// Collects the skipped rows of every faculty and exam session of the schedule.
+    pub fn of(schedule: &Schedule) -> Self {
+        let faculties = schedule.faculties
+            .iter()
+            .map(|faculty| (faculty.name.clone(), faculty.skipped_rows.clone()));
+
+        let sessions = schedule.exam_sessions
+            .iter()
+            .map(|session| (format!("{} (exam session)", session.faculty), session.skipped_rows.clone()));
+
+        let merged = schedule.faculties
+            .iter()
+            .map(|faculty| (faculty.name.clone(), faculty.merges.clone()))
+            .filter(|(_, merges)| !merges.is_empty())
+            .collect();
+
+        ParseReport {
+            skipped: faculties.chain(sessions).filter(|(_, rows)| !rows.is_empty()).collect(),
+            merged,
+        }
+    }
+
+    /// Returns the partially filled rows, which are likely data-entry mistakes, with their faculty names.
+    pub fn suspicious(&self) -> impl Iterator<Item = (&str, &SkippedRow)> {
+        self.skipped
+            .iter()
+            .flat_map(|(faculty, rows)| rows.iter().map(move |row| (faculty.as_str(), row)))
+            .filter(|(_, row)| row.reason.is_suspicious())
+    }
+}
+
+impl Display for ParseReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.skipped.is_empty() {
+            writeln!(f, "No rows skipped")?;
+        }
+
+        for (faculty, rows) in &self.skipped {
+            writeln!(f, "{faculty}:")?;
+
+            for row in rows {
+                writeln!(f, "  {row}")?;
+            }
+        }
+
+        for (faculty, merges) in &self.merged {
+            writeln!(f, "{faculty} (merged rows):")?;
+
+            for merge in merges {
+                writeln!(f, "  {merge}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::schedule::{FacultyMeta, SpecialityName};
+
+    /// Creates the cells of a worksheet row, leaving empty strings as empty cells.
+    fn row(cells: [&str; 6]) -> Vec<Data> {
+        cells
+            .iter()
+            .map(|cell| if cell.is_empty() { Data::Empty } else { Data::String(cell.to_string()) })
+            .collect()
+    }
+
+    #[test]
+    fn classifies_incomplete_lesson_rows() {
+        let cases = [
+            (["", "", "", "", "", ""], Some(SkipReason::Blank)),
+            (["Понеділок", "08:30-09:50", "", "", "", ""], Some(SkipReason::EmptySlot)),
+            (["", "08:30-09:50", "", "1", "1-13", "1-225"], Some(SkipReason::MissingDiscipline)),
+            (["", "08:30-09:50", "Алгебра", " ", "1-13", "1-225"], Some(SkipReason::MissingGroup)),
+            (["", "08:30-09:50", "Алгебра", "1", "", "1-225"], Some(SkipReason::MissingWeeks)),
+            (["", "08:30-09:50", "Алгебра", "1", "1-13", ""], None),
+        ];
+
+        for (cells, reason) in cases {
+            assert_eq!(SkipReason::of_lesson_row(&row(cells)), reason, "{cells:?}");
+        }
+    }
+
+    #[test]
+    fn reports_skipped_rows_and_their_suspicious_ones() {
+        let csv = "День;Час;Дисципліна;Група;Тижні;Аудиторія\n\
+            Понеділок;08:30-09:50;Алгебра;лекція;1-13;1-225\n\
+            ;10:00-11:20;;;;\n\
+            ;;;;;\n\
+            Вівторок;08:30-09:50;Статистика;;1-13;3-205\n";
+        let meta = FacultyMeta { name: "ФЕН".to_owned(), speciality: Some(SpecialityName::Economics) };
+        let schedule = Schedule::from_sources([(Cursor::new(csv), meta)]).unwrap();
+        let report = ParseReport::of(&schedule);
+
+        assert_eq!(report.to_string(), "\
+ФЕН:
+  sheet `Аркуш1`, row 3: time slot without a lesson
+  sheet `Аркуш1`, row 4: blank row
+  sheet `Аркуш1`, row 5: partially filled row without a group
+");
+        assert_eq!(
+            report.suspicious().map(|(faculty, row)| (faculty, row.row)).collect::<Vec<_>>(),
+            [("ФЕН", 5)],
+        );
+    }
+
+    #[test]
+    fn reports_schedules_without_skipped_rows() {
+        assert_eq!(ParseReport::of(&Schedule::default()).to_string(), "No rows skipped\n");
+    }
+}
@@ -1,28 +1,45 @@
 use std::fmt::Display;
 use std::vec;
-use std::{path::Path, collections::HashMap};
+use std::{path::Path, collections::BTreeMap};
 use std::str::FromStr;
+use std::io::{Read, Seek};
 use serde::{Serialize, Deserialize};
-use calamine::{open_workbook, Error, Xlsx, Reader, DataType};
+use calamine::{Error, Data, Range};
+use rayon::prelude::*;
 
 use crate::{
     group::*, 
+    exam::ExamSession,
+    id::{slug, content_id},
+    metadata::{Metadata, SourceFile},
     macros::impl_serde_display_fromstr,
+    report::{GroupMerge, SkipReason, SkippedRow},
+    spreadsheet::{read_worksheets, read_worksheets_from, header_row, title_rows, COLUMNS},
     error::{ScheduleResult, ScheduleError}
 };
 
 /// Represents a university schedule, including information 
 /// about university faculties.
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
 pub struct Schedule {
+    /// The academic year, term, course and level of the schedule, with its sources.
+    #[serde(rename = "Метадані", default, skip_serializing_if = "Metadata::is_empty")]
+    pub metadata: Metadata,
     /// University faculties presented in schedule. Can be 
     /// parsed and added manually as well.
     #[serde(rename = "Факультети")]
     pub faculties: Vec<Faculty>,
+    /// Exam/credit sessions of faculties, parsed from spreadsheets
+    /// with `Дата | Час | Дисципліна | Група | Аудиторія` columns.
+    #[serde(rename = "Сесії", default, skip_serializing_if = "Vec::is_empty")]
+    pub exam_sessions: Vec<ExamSession>,
 }
 
 impl Schedule {
-    /// Creates a new `Schedule` by parsing university schedules from Excel files.
+    /// Creates a new `Schedule` by parsing university schedules from spreadsheet files.
+    ///
+    /// Spreadsheets with `Дата` header are parsed as exam sessions. Week patterns such as `непарні`
+    /// last until the end of a semester of `Weeks::DEFAULT_SEMESTER_WEEKS` weeks.
     ///
     /// # Arguments
     ///
@@ -31,14 +48,186 @@ impl Schedule {
     /// # Returns
     ///
     /// A `Result` containing the parsed `Schedule` if successful, or an error if parsing fails.
-    pub fn new<P: AsRef<Path>>(paths: &[P]) -> ScheduleResult<Self> {
-        let mut faculties = vec![];
+    pub fn new<P: AsRef<Path> + Sync>(paths: &[P]) -> ScheduleResult<Self> {
+        Self::with_overrides(paths, &MetaOverrides::default())
+    }
+
+    /// Creates a new `Schedule` by parsing university schedules from spreadsheet files,
+    /// overriding the faculty metadata detected from file names and title rows.
+    ///
+    /// Files with the `.json` extension are read as previously written `schedule.json` files.
+    /// Files are parsed concurrently, while the order of faculties follows the order of paths.
+    ///
+    /// # Arguments
+    ///
+    /// * `paths`: A slice of paths to spreadsheet files containing faculty schedules.
+    /// * `overrides`: The metadata to set for every parsed faculty.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the parsed `Schedule` if successful, or the error of the first
+    /// file which cannot be parsed.
+    pub fn with_overrides<P: AsRef<Path> + Sync>(paths: &[P], overrides: &MetaOverrides) -> ScheduleResult<Self> {
+        let mut schedule = Schedule::default();
+
+        for parsed in Self::parse_each(paths, overrides, Weeks::DEFAULT_SEMESTER_WEEKS) {
+            schedule.merge(parsed?);
+        }
+
+        Ok(schedule)
+    }
+
+    /// Parses every file into a separate schedule concurrently, keeping the order of paths,
+    /// so that the results can be reported per file.
+    ///
+    /// # Arguments
+    ///
+    /// * `paths`: A slice of paths to spreadsheet files containing faculty schedules.
+    /// * `overrides`: The metadata to set for every parsed faculty.
+    /// * `semester_weeks`: The number of studying weeks of the semester, bounding week patterns such as `непарні`.
+    ///
+    /// # Returns
+    ///
+    /// A result for every path, in the order of paths.
+    pub fn parse_each<P: AsRef<Path> + Sync>(
+        paths: &[P],
+        overrides: &MetaOverrides,
+        semester_weeks: u8,
+    ) -> Vec<ScheduleResult<Self>> {
+        paths
+            .par_iter()
+            .map(|path| Self::parse_file(path.as_ref(), overrides, semester_weeks))
+            .collect()
+    }
+
+    /// Parses a single spreadsheet or JSON schedule file.
+    fn parse_file(path: &Path, overrides: &MetaOverrides, semester_weeks: u8) -> ScheduleResult<Self> {
+        if path.extension().is_some_and(|e| e.eq_ignore_ascii_case("json")) {
+            let mut schedule: Schedule = serde_json::from_reader(std::io::BufReader::new(std::fs::File::open(path)?))?;
+            schedule.faculties.iter_mut().for_each(Faculty::assign_ids);
+
+            return Ok(schedule);
+        }
+
+        let mut schedule = Schedule::default();
+        let worksheets = read_worksheets(path)?;
+        let meta = FacultyMeta::detect(path, &worksheets, overrides)?;
+
+        let mut texts: Vec<String> = path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).into_iter().collect();
+        texts.extend(worksheets.iter().flat_map(|(_, range)| title_rows(range)));
+        schedule.metadata = Metadata::detect(&texts);
+        schedule.metadata.sources.push(SourceFile::of(path)?);
+
+        schedule.add_worksheets(worksheets, meta, semester_weeks)?;
+
+        Ok(schedule)
+    }
+
+    /// Describes the faculties (with their specialities) and exam sessions of the schedule,
+    /// e.g. `Факультет Економічних Наук (Економіка, Фінанси); Факультет Інформатики (exam session)`.
+    pub fn describe(&self) -> String {
+        let faculties = self.faculties.iter().map(|faculty| {
+            let specialities: Vec<String> = faculty.specialities.keys().map(ToString::to_string).collect();
+            format!("{} ({})", faculty.name, specialities.join(", "))
+        });
+
+        let sessions = self.exam_sessions
+            .iter()
+            .map(|session| format!("{} (exam session)", session.faculty));
+
+        faculties.chain(sessions).collect::<Vec<String>>().join("; ")
+    }
+
+    /// Appends the faculties and exam sessions of another schedule,
+    /// filling the missing metadata and appending the sources.
+    pub fn merge(&mut self, other: Schedule) {
+        self.metadata = std::mem::take(&mut self.metadata).or(other.metadata);
+        self.faculties.extend(other.faculties);
+        self.exam_sessions.extend(other.exam_sessions);
+    }
+
+    /// Creates a new `Schedule` by parsing university schedules from readers
+    /// (e.g. in-memory uploaded spreadsheets) with explicitly supplied metadata.
+    ///
+    /// # Arguments
+    ///
+    /// * `sources`: Spreadsheet readers paired with their faculty metadata.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the parsed `Schedule` if successful, or an error if parsing fails.
+    pub fn from_sources<R, I>(sources: I) -> ScheduleResult<Self>
+    where
+        R: Read + Seek,
+        I: IntoIterator<Item = (R, FacultyMeta)>,
+    {
+        let mut schedule = Schedule::default();
 
-        for path in paths {
-            faculties.push(Faculty::new(path.as_ref())?);
+        for (reader, meta) in sources {
+            schedule.add_worksheets(read_worksheets_from(reader)?, meta, Weeks::DEFAULT_SEMESTER_WEEKS)?;
         }
 
-        Ok(Schedule { faculties })
+        Ok(schedule)
+    }
+
+    /// Returns every numbered room used by lessons of the schedule, in ascending order.
+    pub fn rooms(&self) -> Vec<AuditoriumNumber> {
+        let mut rooms: Vec<AuditoriumNumber> = self.groups()
+            .filter_map(|group| group.auditorium.room().cloned())
+            .collect();
+        rooms.sort_unstable();
+        rooms.dedup();
+        rooms
+    }
+
+    /// Finds the rooms of the schedule which are not occupied at the given time.
+    ///
+    /// # Arguments
+    ///
+    /// * `day`: The day of the week.
+    /// * `time`: The time range which must be free.
+    /// * `week`: The studying week. If `None`, rooms must be free during every week.
+    ///
+    /// # Returns
+    ///
+    /// Free rooms in ascending order.
+    pub fn free_rooms(&self, day: Day, time: LessonTime, week: Option<u8>) -> Vec<AuditoriumNumber> {
+        let busy: Vec<&AuditoriumNumber> = self.groups()
+            .filter(|group| group.day == day && group.time.from < time.to && time.from < group.time.to)
+            .filter(|group| week.is_none_or(|week| group.weeks.contains(week)))
+            .filter_map(|group| group.auditorium.room())
+            .collect();
+
+        self.rooms()
+            .into_iter()
+            .filter(|room| !busy.contains(&room))
+            .collect()
+    }
+
+    /// Returns every lesson group of every faculty of the schedule.
+    fn groups(&self) -> impl Iterator<Item = &Group> {
+        self.faculties
+            .iter()
+            .flat_map(|faculty| faculty.specialities.values())
+            .flat_map(|speciality| speciality.disciplines.values())
+            .flat_map(|discipline| &discipline.groups)
+    }
+
+    /// Parses the worksheets of a spreadsheet as an exam session (if it has
+    /// exam session worksheets) or as a faculty weekly schedule, and adds it to the schedule.
+    fn add_worksheets(
+        &mut self,
+        worksheets: Vec<(String, Range<Data>)>,
+        meta: FacultyMeta,
+        semester_weeks: u8,
+    ) -> ScheduleResult<()> {
+        if worksheets.iter().any(|(_, range)| ExamSession::is_session_sheet(range)) {
+            self.exam_sessions.push(ExamSession::from_worksheets(&worksheets, meta)?);
+        } else {
+            self.faculties.push(Faculty::from_worksheets(worksheets, meta, semester_weeks)?);
+        }
+
+        Ok(())
     }
 }
 
@@ -48,56 +237,228 @@ impl_serde_display_fromstr!(SpecialityName);
 /// a collection of specialities with their schedules.
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Faculty {
+    /// The identifier of the faculty, a slug of its name.
+    #[serde(rename = "Ідентифікатор", default)]
+    pub id: String,
     /// The name of the faculty.
     #[serde(rename = "Назва факультету")]
-    name: String,
+    pub name: String,
     /// A mapping of speciality names to their associated specialities.
     #[serde(rename = "Cпеціальності")]
-    specialities: Specialities,
+    pub specialities: Specialities,
+    /// Worksheet rows which were not parsed into lessons.
+    #[serde(skip)]
+    pub skipped_rows: Vec<SkippedRow>,
+    /// Duplicate rows merged into single lesson groups.
+    #[serde(skip)]
+    pub merges: Vec<GroupMerge>,
 }
 
 impl Faculty {
-    /// Creates a new `Faculty` by parsing faculty data from an Excel file.
+    /// The name of the only worksheet of the spreadsheets published by the university.
+    pub const LEGACY_SHEET: &'static str = "Аркуш1";
+
+    /// Creates a new `Faculty` by parsing faculty data from a spreadsheet file.
+    ///
+    /// Supported formats are `.xlsx`, `.xls`, `.xlsb`, `.ods` and `.csv`.
     ///
     /// # Arguments
     ///
-    /// * `path`: The path to the Excel file containing faculty schedule data.
+    /// * `path`: The path to the spreadsheet file containing faculty schedule data.
     ///
     /// # Returns
     ///
     /// A `Result` containing the parsed `Faculty` if successful, or an error if parsing fails.
     pub fn new(path: &Path) -> ScheduleResult<Self> {
-        // Get faculty name (and optionally a defined speciality name)
-        let (name, mut specialities) = {
-            let elements: Vec<&str> = path.to_str().unwrap().split('.').collect();
-            let mut specialities = Specialities::new();
-
-            if elements.len() == 3 {
-                specialities.insert(
-                    SpecialityName::from_str(elements[1])?,
-                    Speciality { disciplines: Disciplines::new() }
-                );
+        Self::with_overrides(path, &MetaOverrides::default())
+    }
+
+    /// Creates a new `Faculty` by parsing faculty data from a spreadsheet file,
+    /// overriding the metadata detected from the file name and title rows.
+    ///
+    /// # Arguments
+    ///
+    /// * `path`: The path to the spreadsheet file containing faculty schedule data.
+    /// * `overrides`: The metadata to set explicitly.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the parsed `Faculty` if successful, or an error if parsing fails.
+    pub fn with_overrides(path: &Path, overrides: &MetaOverrides) -> ScheduleResult<Self> {
+        let worksheets = read_worksheets(path)?;
+        let meta = FacultyMeta::detect(path, &worksheets, overrides)?;
+
+        Self::from_worksheets(worksheets, meta, Weeks::DEFAULT_SEMESTER_WEEKS)
+    }
+
+    /// Creates a new `Faculty` by parsing faculty data from an in-memory spreadsheet
+    /// or any other reader (e.g. an uploaded file).
+    ///
+    /// The spreadsheet format is detected by the content, as there is no file name.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader`: The source of the spreadsheet content.
+    /// * `meta`: The faculty name and optionally a defined speciality.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the parsed `Faculty` if successful, or an error if parsing fails.
+    pub fn from_reader<R: Read + Seek>(reader: R, meta: FacultyMeta) -> ScheduleResult<Self> {
+        Self::from_worksheets(read_worksheets_from(reader)?, meta, Weeks::DEFAULT_SEMESTER_WEEKS)
+    }
+
+    /// Creates a new `Faculty` from the worksheets of a spreadsheet.
+    ///
+    /// The legacy `Аркуш1` sheet takes the speciality from the metadata (if any),
+    /// while canonical workbooks have a sheet per speciality. Week patterns such as `непарні`
+    /// last until the end of a semester of `semester_weeks` weeks.
+    fn from_worksheets(
+        worksheets: Vec<(String, Range<Data>)>,
+        meta: FacultyMeta,
+        semester_weeks: u8,
+    ) -> ScheduleResult<Self> {
+        let FacultyMeta { name, speciality: defined_speciality } = meta;
+        let mut specialities = Specialities::new();
+
+        if let Some(speciality) = defined_speciality {
+            specialities.insert(speciality, Speciality { id: String::new(), disciplines: Disciplines::new() });
+        }
+
+        let mut sheets = vec![];
+        for (sheet, range) in worksheets {
+            if sheet == Self::LEGACY_SHEET {
+                sheets.push((sheet, range, defined_speciality));
+            } else if let Some(speciality) = SpecialityName::from_abbreviation(&sheet) {
+                // Speciality sheets without lessons are kept as specialities without disciplines
+                specialities
+                    .entry(speciality)
+                    .or_insert_with(|| Speciality { id: String::new(), disciplines: Disciplines::new() });
+                sheets.push((sheet, range, Some(speciality)));
             }
+        }
 
-            (elements[0].to_owned(), specialities)
-        };
+        if sheets.is_empty() {
+            Err(Error::Msg("Cannot find 'Аркуш1' or speciality sheets"))?;
+        }
+
+        let mut skipped_rows = vec![];
+        for (sheet, range, speciality) in sheets {
+            Self::parse_sheet(&sheet, &range, speciality, semester_weeks, &mut specialities, &mut skipped_rows)?;
+        }
+
+        for speciality in specialities.values_mut() {
+            for discipline in speciality.disciplines.values_mut() {
+                discipline.sort_groups();
+            }
+        }
 
-        let mut workbook: Xlsx<_> = open_workbook(path)
-            .map_err(Error::from)?;
-        let range = workbook.worksheet_range("Аркуш1")
-            .ok_or(Error::Msg("Cannot find 'Аркуш1' sheet"))?
-            .map_err(Error::from)?;
+        let mut faculty = Faculty { id: String::new(), name, specialities, skipped_rows, merges: vec![] };
+        faculty.assign_ids();
 
+        Ok(faculty)
+    }
+
+    /// Merges duplicate rows of every discipline (see `Discipline::merge_duplicates`),
+    /// recording every merge, and recomputes the identifiers.
+    ///
+    /// Not done by default, so the parsed schedule keeps every row of the spreadsheet.
+    pub fn merge_duplicates(&mut self) {
+        for speciality in self.specialities.values_mut() {
+            for (name, discipline) in &mut speciality.disciplines {
+                // Shared lectures are copied into every speciality, so their merges are reported once
+                for merge in discipline.merge_duplicates(name) {
+                    if !self.merges.contains(&merge) {
+                        self.merges.push(merge);
+                    }
+                }
+
+                discipline.sort_groups();
+            }
+        }
+
+        self.assign_ids();
+    }
+
+    /// Computes the identifiers of the faculty, its specialities, disciplines and lesson groups.
+    ///
+    /// Identifiers are derived from names and lesson contents only, so they are
+    /// the same on every run and do not change with whitespace edits of the spreadsheet.
+    /// The speciality is not a part of lesson identifiers, so a lecture shared by several
+    /// specialities has the same identifier in each of them. Identical lessons of a discipline
+    /// get distinct identifiers by their order.
+    pub fn assign_ids(&mut self) {
+        self.id = slug(&self.name);
+
+        for (name, speciality) in &mut self.specialities {
+            speciality.id = slug(&name.to_string());
+
+            for (name, discipline) in &mut speciality.disciplines {
+                discipline.id = slug(name);
+
+                let mut occurrences: BTreeMap<String, usize> = BTreeMap::new();
+
+                for group in &mut discipline.groups {
+                    let id = content_id(&[
+                        &self.id,
+                        &discipline.id,
+                        &group.name.to_string(),
+                        &group.day.to_string(),
+                        &group.time.to_string(),
+                        &group.weeks.to_string(),
+                        &group.auditorium.to_string(),
+                    ]);
+
+                    let occurrence = occurrences.entry(id.clone()).or_default();
+                    *occurrence += 1;
+
+                    group.id = match *occurrence {
+                        1 => id,
+                        n => content_id(&[&id, &n.to_string()]),
+                    };
+                }
+            }
+        }
+    }
+
+    /// Parses lesson rows of a worksheet into the specialities collection.
+    ///
+    /// # Arguments
+    ///
+    /// * `sheet`: The name of the worksheet.
+    /// * `range`: The cells of the worksheet.
+    /// * `defined_speciality`: The speciality all lessons of the worksheet belong to.
+    ///   If `None`, specialities are parsed from discipline names.
+    /// * `semester_weeks`: The number of studying weeks of the semester, bounding week patterns.
+    /// * `specialities`: The collection to insert parsed disciplines into.
+    /// * `skipped_rows`: The collection to record rows which are not lessons into.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` indicating success or failure of the parsing.
+    fn parse_sheet(
+        sheet: &str,
+        range: &Range<Data>,
+        defined_speciality: Option<SpecialityName>,
+        semester_weeks: u8,
+        specialities: &mut Specialities,
+        skipped_rows: &mut Vec<SkippedRow>,
+    ) -> ScheduleResult<()> {
         let mut reserved_day = Day::default();
         let mut reserved_time = LessonTime::default();
 
-        let defined_speciality = !specialities.is_empty();
+        // Skip title rows above the header
+        let first_row = header_row(range).map_or(0, |header| header + 1);
+        let start_row = range.start().map_or(0, |(row, _)| row);
+
+        for (index, row) in range.rows().enumerate().skip(first_row) {
+            // Worksheets may be narrower than the expected columns (e.g. without the auditorium column)
+            let cell = |i: usize| row.get(i).unwrap_or(&Data::Empty);
 
-        for row in range.rows() {
             // Get day of the week
-            let day = match &row[0] {
-                DataType::String(s) => {
-                    // Skip first header row
+            let day = match cell(0) {
+                Data::String(s) => {
+                    // Skip repeated header rows
                     if s == "День" {
                         continue;
                     } else {
@@ -109,97 +470,230 @@ impl Faculty {
             };
             
             // Get lesson time
-            let time = match &row[1] {
-                DataType::String(s) => {
+            let time = match cell(1) {
+                Data::String(s) => {
                     reserved_time = LessonTime::from_str(s)?;
                     reserved_time
                 },
                 _ => reserved_time,
             };
 
-            // Get lesson type (group number or a lection)
-            let name = match &row[3] {
-                DataType::String(s) => LessonType::from_str(s)?,
-                DataType::Int(number) => LessonType::Classes(*number as u8),
-                DataType::Float(number) => LessonType::Classes(*number as u8),
-                DataType::Empty => continue,
-                _ => Err(ScheduleError::InvalidLessonType(row[3].to_string()))?,
+            // Record rows without lessons: blank separators, empty time slots and incomplete rows
+            if let Some(reason) = SkipReason::of_lesson_row(&row[..row.len().min(COLUMNS)]) {
+                skipped_rows.push(SkippedRow {
+                    sheet: sheet.to_owned(),
+                    row: start_row + index as u32 + 1,
+                    reason,
+                });
+                continue;
+            }
+
+            // Get lesson type (group numbers, a lection, a lab etc.)
+            let name = match cell(3) {
+                Data::String(s) => LessonType::from_str(s)?,
+                Data::Int(number) => LessonType::seminar(*number as u8),
+                Data::Float(number) => LessonType::seminar(*number as u8),
+                _ => Err(ScheduleError::InvalidLessonType(cell(3).to_string()))?,
             };
 
             // Get studying weeks
-            let weeks = match &row[4] {
-                DataType::String(s) => Weeks::from_str(s)?,
-                DataType::Int(number) => Weeks::Single(*number as u8),
-                DataType::Float(number) => Weeks::Single(*number as u8),
-                DataType::Empty => continue,
-                _ => Err(ScheduleError::InvalidWeeksFormat(row[4].to_string()))?,
+            let weeks = match cell(4) {
+                Data::String(s) => Weeks::parse(s, semester_weeks)?,
+                Data::Int(number) => Weeks::Single(*number as u8),
+                Data::Float(number) => Weeks::Single(*number as u8),
+                _ => Err(ScheduleError::InvalidWeeksFormat(cell(4).to_string()))?,
             };
 
-            // Get auditorium number (may be also art center or distance)
-            let auditorium = match &row[5] {
-                DataType::String(s) => Auditorium::from_str(s)?,
-                _ => Err(ScheduleError::InvalidAuditorium(row[5].to_string()))?
+            // Get auditorium number (may be also art center or distance), not known yet if empty
+            let auditorium = match cell(5) {
+                Data::String(s) if s.trim().is_empty() => Auditorium::ToBeAnnounced,
+                Data::String(s) => Auditorium::from_str(s)?,
+                Data::Empty => Auditorium::ToBeAnnounced,
+                _ => Err(ScheduleError::InvalidAuditorium(cell(5).to_string()))?
             };
 
-            let group = Group { name, time, weeks, auditorium, day };
+            let group = Group { name, time, weeks, auditorium, day, ..Default::default() };
 
-            // If speciality defined in the filename, use it in parsing
-            // and just copy discipline name without processing.
+            // If speciality defined in the filename or sheet name, use it
+            // in parsing and just copy discipline name without processing.
             //
             // Else if not defined, parse speciality names from
             // discipline names and write them to the specialities collection.
             // If not present, prefer `General` speciality (for common
             // lection attendance)
-            if defined_speciality {
-                let discipline = row[2].to_string().replace("  ", " ").replace('\n', "");
-                let spec = specialities.values_mut().next().unwrap();
-                match spec.disciplines.get_mut(&discipline) {
-                    Some(disc) => disc.groups.push(group),
-                    _ => {
-                        spec.disciplines.insert(discipline, Discipline { 
-                            groups: vec![group]
-                        });
-                    }
-                }
-            } else {
-                let discipline = row[2].to_string().replace("  ", " ").replace('\n', "");
-                let speciality_names = SpecialityName::from_discipline(&discipline);
-                
-                for name in speciality_names {
-                    if let Some(spec) = specialities.get_mut(&name) {
-                        match spec.disciplines.get_mut(&discipline) {
-                            Some(disc) => disc.groups.push(group.clone()),
-                            _ => {
-                                spec.disciplines.insert(discipline.clone(), Discipline { 
-                                    groups: vec![group.clone()]
-                                });
-                            }
-                        }
-                    } else {
-                        specialities.insert(name, Speciality {
-                            disciplines: [(discipline.clone(), Discipline {
-                                groups: vec![group.clone()],
-                            })].into()
-                        });
-                    }
-                }
+            let discipline = cell(2).to_string().replace("  ", " ").replace('\n', "");
+            let speciality_names = match defined_speciality {
+                Some(speciality) => vec![speciality],
+                None => SpecialityName::from_discipline(&discipline),
+            };
+
+            for name in speciality_names {
+                specialities
+                    .entry(name)
+                    .or_insert_with(|| Speciality { id: String::new(), disciplines: Disciplines::new() })
+                    .disciplines
+                    .entry(discipline.clone())
+                    .or_insert_with(|| Discipline { id: String::new(), groups: vec![] })
+                    .groups
+                    .push(group.clone());
             }
         }
 
-        Ok(Faculty { name, specialities })
+        Ok(())
+    }
+}
+
+/// Represents the metadata of a faculty spreadsheet, which is not stored in the spreadsheet itself.
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+pub struct FacultyMeta {
+    /// The name of the faculty.
+    pub name: String,
+    /// The speciality all lessons of the spreadsheet belong to.
+    /// If `None`, specialities are parsed from discipline names.
+    pub speciality: Option<SpecialityName>,
+}
+
+impl FacultyMeta {
+    /// Gets the faculty name (and optionally a defined speciality name) from
+    /// a spreadsheet file name in `<faculty>.<speciality>.xlsx` or `<faculty>.xlsx` format.
+    ///
+    /// # Arguments
+    ///
+    /// * `path`: The path to the spreadsheet file. Only the file name is used.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the metadata, or an error if the path is not valid UTF-8.
+    /// A part after the last dot which does not name a speciality belongs to the faculty name.
+    pub fn from_path(path: &Path) -> ScheduleResult<Self> {
+        let (name, speciality) = Self::split_file_name(path)?;
+
+        Ok(FacultyMeta {
+            name: name.to_owned(),
+            speciality: speciality.map(SpecialityName::from_str).transpose()?,
+        })
+    }
+
+    /// Detects the metadata of a spreadsheet file.
+    ///
+    /// The explicitly set overrides take precedence. Otherwise the file name is used,
+    /// falling back to the title rows above the header when the file name
+    /// does not name a faculty or a speciality.
+    ///
+    /// # Arguments
+    ///
+    /// * `path`: The path to the spreadsheet file.
+    /// * `worksheets`: The worksheets of the spreadsheet.
+    /// * `overrides`: The metadata to set explicitly.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the metadata, or an error if the path is not valid UTF-8
+    /// or the speciality is unknown.
+    pub fn detect(
+        path: &Path,
+        worksheets: &[(String, Range<Data>)],
+        overrides: &MetaOverrides,
+    ) -> ScheduleResult<Self> {
+        let (file_faculty, file_speciality) = Self::split_file_name(path)?;
+        let titles: Vec<String> = worksheets
+            .iter()
+            .flat_map(|(_, range)| title_rows(range))
+            .collect();
+
+        let name = match &overrides.faculty {
+            Some(faculty) => faculty.clone(),
+            None if !file_faculty.to_lowercase().contains("факультет") => {
+                Self::faculty_from_titles(&titles).unwrap_or_else(|| file_faculty.to_owned())
+            },
+            None => file_faculty.to_owned(),
+        };
+
+        let speciality = match (overrides.speciality, file_speciality) {
+            (Some(speciality), _) => Some(speciality),
+            (None, Some(speciality)) => Some(SpecialityName::from_str(speciality)?),
+            (None, None) => Self::speciality_from_titles(&titles),
+        };
+
+        Ok(FacultyMeta { name, speciality })
+    }
+
+    /// Splits the file name stem into the faculty and optional speciality parts.
+    ///
+    /// The part after the last dot is the speciality only if it names one,
+    /// so faculty names may contain dots (e.g. `Ф.І.` in `Ф.І..Економіка.xlsx` or `Ф.І..xlsx`).
+    fn split_file_name(path: &Path) -> ScheduleResult<(&str, Option<&str>)> {
+        let stem = path
+            .file_stem()
+            .ok_or_else(|| ScheduleError::InvalidPath(path.display().to_string()))?
+            .to_str()
+            .ok_or_else(|| ScheduleError::InvalidPath(path.display().to_string()))?;
+
+        match stem.rsplit_once('.') {
+            Some((faculty, speciality)) if SpecialityName::from_str(speciality.trim()).is_ok() => {
+                Ok((faculty.trim(), Some(speciality.trim())))
+            },
+            _ => Ok((stem.trim(), None)),
+        }
+    }
+
+    /// Finds the faculty name in title rows (e.g. `Розклад занять. Факультет інформатики`).
+    fn faculty_from_titles(titles: &[String]) -> Option<String> {
+        titles.iter().find_map(|title| {
+            let position = ["Факультет", "факультет", "ФАКУЛЬТЕТ"]
+                .iter()
+                .find_map(|word| title.find(word))?;
+            let name = title[position..].lines().next()?.trim();
+
+            let mut chars = name.chars();
+            let first = chars.next()?;
+
+            Some(first.to_uppercase().chain(chars).collect())
+        })
     }
+
+    /// Finds the only speciality mentioned in title rows.
+    /// If several specialities are mentioned, they are parsed from discipline names.
+    fn speciality_from_titles(titles: &[String]) -> Option<SpecialityName> {
+        let mut found: Vec<SpecialityName> = SpecialityName::ALL
+            .into_iter()
+            .filter(|speciality| *speciality != SpecialityName::General)
+            .filter(|speciality| {
+                let name = speciality.to_string().to_lowercase();
+                titles.iter().any(|title| title.to_lowercase().contains(&name))
+            })
+            .collect();
+
+        match found.len() {
+            1 => found.pop(),
+            _ => None,
+        }
+    }
+}
+
+/// Represents faculty metadata set explicitly (e.g. with command-line flags),
+/// which overrides the metadata detected from file names and title rows.
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+pub struct MetaOverrides {
+    /// The name of the faculty.
+    pub faculty: Option<String>,
+    /// The speciality all lessons of the spreadsheet belong to.
+    pub speciality: Option<SpecialityName>,
 }
 
 /// Represents a university speciality, including a collection of disciplines.
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Speciality {
+    /// The identifier of the speciality, a slug of its name.
+    #[serde(rename = "Ідентифікатор", default)]
+    pub id: String,
     /// A mapping of discipline names to their associated groups.
     #[serde(rename = "Дисципліни")]
-    disciplines: Disciplines,
+    pub disciplines: Disciplines,
 }
 
 /// Represents the names of university specialities.
-#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum SpecialityName {
     /// Software Engineering speciality of Faculty of Informatics.
     SoftwareEngineering,
@@ -221,8 +715,8 @@ impl SpecialityName {
     ///
     /// # Arguments
     ///
-    /// * `discipline`: The name of the discipline with defined 
-    /// specialities (e.g. `(марк.)`, `(екон.+фін.)`).
+    /// * `discipline`: The name of the discipline with defined
+    ///   specialities (e.g. `(марк.)`, `(екон.+фін.)`).
     ///
     /// # Returns
     ///
@@ -275,6 +769,36 @@ impl SpecialityName {
 
         names
     }
+
+    /// Every known speciality name.
+    pub const ALL: [SpecialityName; 6] = [
+        SpecialityName::SoftwareEngineering,
+        SpecialityName::Economics,
+        SpecialityName::Management,
+        SpecialityName::Finances,
+        SpecialityName::Marketing,
+        SpecialityName::General,
+    ];
+
+    /// Returns the abbreviation of the speciality, which is short enough
+    /// to be used as a worksheet name.
+    pub fn abbreviation(&self) -> &'static str {
+        use SpecialityName::*;
+
+        match self {
+            SoftwareEngineering => "ІПЗ",
+            Economics => "екон.",
+            Management => "мен.",
+            Finances => "фін.",
+            Marketing => "марк.",
+            General => "загальна",
+        }
+    }
+
+    /// Finds a speciality by its abbreviation.
+    pub fn from_abbreviation(abbreviation: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|s| s.abbreviation() == abbreviation)
+    }
 }
 
 impl Display for SpecialityName {
@@ -311,15 +835,424 @@ impl FromStr for SpecialityName {
 }
 
 /// A mapping of university speciality names to their associated specialities.
-pub type Specialities = HashMap<SpecialityName, Speciality>;
+///
+/// Ordered, so the serialized schedule is the same on every run.
+pub type Specialities = BTreeMap<SpecialityName, Speciality>;
 
 /// Represents a university discipline, including a list of associated groups.
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Discipline {
+    /// The identifier of the discipline, a slug of its full name.
+    #[serde(rename = "Ідентифікатор", default)]
+    pub id: String,
     /// A list of student groups associated with this discipline.
     #[serde(rename = "Групи")]
-    groups: Vec<Group>,
+    pub groups: Vec<Group>,
+}
+
+impl Discipline {
+    /// Teacher title markers, which usually follow the discipline name in spreadsheets.
+    const TEACHER_TITLES: [&'static str; 8] = [
+        " ст.викл", " ст. викл", " cт. викл", " доц.", " проф.", " асист.", " ас.", " викл.",
+    ];
+
+    /// Shortens a full discipline name, as written in spreadsheets, to the discipline
+    /// title only, dropping speciality marks and teacher names.
+    ///
+    /// # Arguments
+    ///
+    /// * `name`: The full discipline name (e.g. `Страхування (фін.) доц. Бридун Є.В.`).
+    ///
+    /// # Returns
+    ///
+    /// The short discipline name (e.g. `Страхування`).
+    pub fn short_name(name: &str) -> &str {
+        let mut end = name.find(['(', ',']).unwrap_or(name.len());
+
+        for title in Self::TEACHER_TITLES {
+            if let Some(position) = name[..end].find(title) {
+                end = position;
+            }
+        }
+
+        name[..end].trim()
+    }
+
+    /// Merges identical groups and coalesces groups split by weeks (e.g. one lesson split
+    /// into rows with weeks `1-5` and `6-13`) into one group with combined weeks.
+    ///
+    /// Groups are merged only if they have the same lesson type (including its label), day, time
+    /// and auditorium, and either the same weeks, or disjoint weeks which together are consecutive.
+    /// Other groups, e.g. with overlapping weeks, are kept, so the `duplicate-lesson` lint rule reports them.
+    ///
+    /// # Arguments
+    ///
+    /// * `name`: The full name of the discipline, used in the report.
+    ///
+    /// # Returns
+    ///
+    /// Every merge of groups.
+    pub fn merge_duplicates(&mut self, name: &str) -> Vec<GroupMerge> {
+        let mut lessons: Vec<Vec<Group>> = vec![];
+
+        for group in self.groups.drain(..) {
+            let same = lessons.iter().position(|rows| {
+                let other = &rows[0];
+
+                other.name == group.name
+                    && other.name.label() == group.name.label()
+                    && other.day == group.day
+                    && other.time == group.time
+                    && other.auditorium == group.auditorium
+            });
+
+            match same {
+                Some(index) => lessons[index].push(group),
+                None => lessons.push(vec![group]),
+            }
+        }
+
+        let mut merges = vec![];
+
+        for mut rows in lessons {
+            let weeks: Vec<Weeks> = rows.iter().map(|group| group.weeks.clone()).collect();
+
+            let Some(merged) = Self::merged_weeks(&weeks) else {
+                self.groups.extend(rows);
+                continue;
+            };
+
+            let mut group = rows.swap_remove(0);
+
+            if weeks.len() > 1 {
+                group.weeks = merged;
+
+                merges.push(GroupMerge {
+                    discipline: name.to_owned(),
+                    lesson: group.name.to_string(),
+                    day: group.day,
+                    time: group.time,
+                    weeks,
+                    merged: group.weeks.clone(),
+                });
+            }
+
+            self.groups.push(group);
+        }
+
+        merges
+    }
+
+    /// Combines the weeks of rows of the same lesson, if they are the same
+    /// or disjoint and consecutive together.
+    ///
+    /// # Arguments
+    ///
+    /// * `weeks`: The weeks of every row.
+    ///
+    /// # Returns
+    ///
+    /// The combined weeks, or `None` if the rows must not be merged.
+    fn merged_weeks(weeks: &[Weeks]) -> Option<Weeks> {
+        let first = weeks.first()?;
+
+        if weeks.iter().all(|other| other.numbers() == first.numbers()) {
+            return Some(first.clone());
+        }
+
+        let numbers: Vec<u8> = weeks.iter().flat_map(Weeks::numbers).collect();
+        let mut union = numbers.clone();
+        union.sort_unstable();
+        union.dedup();
+
+        let disjoint = union.len() == numbers.len();
+        let consecutive = union.windows(2).all(|pair| pair[1] == pair[0] + 1);
+
+        (disjoint && consecutive).then(|| Weeks::from_numbers(&union))
+    }
+
+    /// Sorts groups by day, time and group number, so the order does not
+    /// depend on the order of spreadsheet rows.
+    pub fn sort_groups(&mut self) {
+        self.groups.sort_by(|a, b| {
+            (a.day, a.time, &a.name).cmp(&(b.day, b.time, &b.name))
+        });
+    }
 }
 
 /// A mapping of discipline names to their associated disciplines.
-pub type Disciplines = HashMap<String, Discipline>;
\ No newline at end of file
+///
+/// Ordered, so the serialized schedule is the same on every run.
+pub type Disciplines = BTreeMap<String, Discipline>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{normalized::NormalizedFaculty, spreadsheet::read_csv};
+
+    /// Parses a faculty of the Економіка speciality from CSV rows below the header.
+    fn faculty(rows: &str) -> Faculty {
+        let range = read_csv(&format!("День;Час;Дисципліна;Група;Тижні;Аудиторія\n{rows}")).unwrap();
+        let meta = FacultyMeta { name: "Факультет".to_owned(), speciality: Some(SpecialityName::Economics) };
+
+        Faculty::from_worksheets(vec![(Faculty::LEGACY_SHEET.to_owned(), range)], meta, Weeks::DEFAULT_SEMESTER_WEEKS).unwrap()
+    }
+
+    /// Returns the groups of the only discipline of the faculty.
+    fn groups(faculty: &Faculty) -> &[Group] {
+        &faculty.specialities[&SpecialityName::Economics].disciplines.values().next().unwrap().groups
+    }
+
+    #[test]
+    fn lessons_in_different_rooms_have_different_ids() {
+        let faculty = faculty(
+            "Понеділок;08:30-09:50;Алгебра;лекція;1-13;1-225\n\
+             Понеділок;08:30-09:50;Алгебра;лекція;1-13;3-205\n",
+        );
+        let groups = groups(&faculty);
+
+        assert_ne!(groups[0].id, groups[1].id);
+        assert_eq!(NormalizedFaculty::of(&faculty).lessons.len(), 2);
+    }
+
+    #[test]
+    fn identical_lessons_have_different_ids() {
+        let faculty = faculty(
+            "Понеділок;08:30-09:50;Алгебра;1;1-13;1-225\n\
+             Понеділок;08:30-09:50;Алгебра;1;1-13;1-225\n",
+        );
+        let groups = groups(&faculty);
+        assert_ne!(groups[0].id, groups[1].id);
+    }
+
+    #[test]
+    fn ids_do_not_depend_on_whitespace() {
+        let first = faculty("Понеділок;08:30-09:50;Алгебра;1;1-13;1-225\n");
+        let second = faculty("Понеділок;08:30 - 09:50;Алгебра ;1;1 - 13;1-225\n");
+
+        assert_eq!(groups(&first)[0].id, groups(&second)[0].id);
+    }
+
+    /// Parses a faculty from CSV rows and merges its duplicate rows.
+    fn merged(rows: &str) -> Faculty {
+        let mut faculty = faculty(rows);
+        faculty.merge_duplicates();
+        faculty
+    }
+
+    #[test]
+    fn keeps_duplicate_rows_by_default() {
+        let faculty = faculty(
+            "Понеділок;08:30-09:50;Алгебра;1;1-13;1-225\n\
+             Понеділок;08:30-09:50;Алгебра;1;1-13;1-225\n",
+        );
+
+        assert_eq!(groups(&faculty).len(), 2);
+        assert!(faculty.merges.is_empty());
+    }
+
+    #[test]
+    fn merges_identical_rows() {
+        let faculty = merged(
+            "Понеділок;08:30-09:50;Алгебра;1;1-13;1-225\n\
+             Понеділок;08:30-09:50;Алгебра;1;1-13;1-225\n",
+        );
+
+        assert_eq!(groups(&faculty).len(), 1);
+        assert!(faculty.merges[0].is_identical());
+    }
+
+    #[test]
+    fn coalesces_rows_split_by_weeks() {
+        let faculty = merged(
+            "Понеділок;08:30-09:50;Алгебра;1;6-13;1-225\n\
+             Понеділок;08:30-09:50;Алгебра;1;1-5;1-225\n",
+        );
+
+        assert_eq!(groups(&faculty).len(), 1);
+        assert_eq!(groups(&faculty)[0].weeks.to_string(), "1-13");
+        assert_eq!(faculty.merges[0].to_string(), "`Алгебра` `1` (Понеділок 08:30-09:50): merged weeks 6-13 and 1-5 into 1-13");
+    }
+
+    #[test]
+    fn keeps_rows_with_overlapping_or_separate_weeks() {
+        let overlapping = merged(
+            "Понеділок;08:30-09:50;Алгебра;1;1-7;1-225\n\
+             Понеділок;08:30-09:50;Алгебра;1;5-13;1-225\n",
+        );
+        let separate = merged(
+            "Понеділок;08:30-09:50;Алгебра;1;1-3;1-225\n\
+             Понеділок;08:30-09:50;Алгебра;1;8-13;1-225\n",
+        );
+
+        assert_eq!(groups(&overlapping).len(), 2);
+        assert_eq!(groups(&separate).len(), 2);
+        assert!(overlapping.merges.is_empty() && separate.merges.is_empty());
+    }
+
+    #[test]
+    fn keeps_rows_with_different_labels_or_rooms() {
+        let labels = merged(
+            "Понеділок;08:30-09:50;Алгебра;практ. 1;1-13;1-225\n\
+             Понеділок;08:30-09:50;Алгебра;Практична 1;1-13;1-225\n",
+        );
+        let rooms = merged(
+            "Понеділок;08:30-09:50;Алгебра;1;1-13;1-225\n\
+             Понеділок;08:30-09:50;Алгебра;1;1-13;3-205\n",
+        );
+
+        assert_eq!(groups(&labels).len(), 2);
+        assert_eq!(groups(&rooms).len(), 2);
+    }
+
+    #[test]
+    fn narrow_sheets_do_not_panic() {
+        let csv = read_csv("День;Час;Дисципліна;Група;Тижні\nПонеділок;08:30-09:50;Алгебра;1;1-13\n").unwrap();
+        let mut range = Range::new((0, 0), (1, 4));
+        for (row, column, value) in csv.cells().filter(|(_, column, _)| *column < 5) {
+            range.set_value((row as u32, column as u32), value.clone());
+        }
+
+        let meta = FacultyMeta { name: "Факультет".to_owned(), speciality: Some(SpecialityName::Economics) };
+        let parsed = Faculty::from_worksheets(vec![(Faculty::LEGACY_SHEET.to_owned(), range)], meta, Weeks::DEFAULT_SEMESTER_WEEKS);
+
+        assert_eq!(groups(&parsed.unwrap())[0].auditorium, Auditorium::ToBeAnnounced);
+    }
+
+    #[test]
+    fn empty_auditoriums_are_to_be_announced() {
+        let faculty = faculty(
+            "Понеділок;08:30-09:50;Алгебра;1;1-13;\n\
+             Понеділок;10:00-11:20;Алгебра;2;1-13;  \n",
+        );
+
+        assert!(groups(&faculty).iter().all(|group| group.auditorium == Auditorium::ToBeAnnounced));
+    }
+
+    #[test]
+    fn faculties_are_parsed_from_readers_with_explicit_metadata() {
+        let csv = "День;Час;Дисципліна;Група;Тижні;Аудиторія\nПонеділок;08:30-09:50;Алгебра;лекція;1-13;1-225\n";
+        let meta = FacultyMeta { name: "ФІ".to_owned(), speciality: Some(SpecialityName::Economics) };
+        let faculty = Faculty::from_reader(std::io::Cursor::new(csv), meta).unwrap();
+
+        assert_eq!(faculty.name, "ФІ");
+        assert_eq!(faculty.specialities.keys().collect::<Vec<_>>(), [&SpecialityName::Economics]);
+        assert_eq!(groups(&faculty).len(), 1);
+    }
+
+    #[test]
+    fn schedules_are_parsed_from_in_memory_workbooks() {
+        let faculty = faculty("Понеділок;08:30-09:50;Алгебра;лекція;1-13;1-225\nСереда;10:00-11:20;Алгебра;1;непарні;3-205\n");
+        let dir = std::env::temp_dir().join(format!("naukma_schedule_{}_readers", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let schedule = Schedule { faculties: vec![faculty.clone()], ..Default::default() };
+        let paths = crate::writer::write_schedule(&schedule, &dir).unwrap();
+
+        let workbook = std::io::Cursor::new(std::fs::read(&paths[0]).unwrap());
+        let meta = FacultyMeta { name: "Факультет".to_owned(), speciality: None };
+        let parsed = Schedule::from_sources([(workbook, meta)]).unwrap();
+
+        assert_eq!(parsed.faculties.len(), 1);
+        assert_eq!(parsed.faculties[0].name, "Факультет");
+        assert_eq!(
+            serde_json::to_string(&parsed.faculties[0].specialities).unwrap(),
+            serde_json::to_string(&faculty.specialities).unwrap(),
+        );
+    }
+
+    /// Reads a worksheet from CSV rows, including title rows above the header.
+    fn worksheets(rows: &str) -> Vec<(String, Range<Data>)> {
+        vec![(Faculty::LEGACY_SHEET.to_owned(), read_csv(rows).unwrap())]
+    }
+
+    #[test]
+    fn detects_metadata_from_title_rows() {
+        let worksheets = worksheets(
+            "Розклад занять. Факультет економічних наук;;;;;\n\
+             Спеціальність \"Економіка\", 2 курс;;;;;\n\
+             День;Час;Дисципліна;Група;Тижні;Аудиторія\n",
+        );
+        let meta = FacultyMeta::detect(Path::new("розклад.xlsx"), &worksheets, &MetaOverrides::default()).unwrap();
+
+        assert_eq!(meta, FacultyMeta {
+            name: "Факультет економічних наук".to_owned(),
+            speciality: Some(SpecialityName::Economics),
+        });
+    }
+
+    #[test]
+    fn file_names_take_precedence_over_title_rows() {
+        let worksheets = worksheets("Факультет економічних наук, Економіка;;;;;\nДень;Час;Дисципліна;Група;Тижні;Аудиторія\n");
+        let meta = FacultyMeta::detect(
+            Path::new("Факультет інформатики.Інженерія програмного забезпечення.xlsx"),
+            &worksheets,
+            &MetaOverrides::default(),
+        ).unwrap();
+
+        assert_eq!(meta.name, "Факультет інформатики");
+        assert_eq!(meta.speciality, Some(SpecialityName::SoftwareEngineering));
+    }
+
+    #[test]
+    fn overrides_take_precedence_over_file_names() {
+        let overrides = MetaOverrides { faculty: Some("ФЕН".to_owned()), speciality: Some(SpecialityName::Economics) };
+        let meta = FacultyMeta::detect(
+            Path::new("Факультет інформатики.Інженерія програмного забезпечення.xlsx"),
+            &worksheets("День;Час;Дисципліна;Група;Тижні;Аудиторія\n"),
+            &overrides,
+        ).unwrap();
+
+        assert_eq!(meta, FacultyMeta { name: "ФЕН".to_owned(), speciality: Some(SpecialityName::Economics) });
+    }
+
+    #[test]
+    fn titles_mentioning_several_specialities_do_not_define_one() {
+        let worksheets = worksheets("Економіка та Інженерія програмного забезпечення;;;;;\nДень;Час;Дисципліна;Група;Тижні;Аудиторія\n");
+        let meta = FacultyMeta::detect(Path::new("розклад.xlsx"), &worksheets, &MetaOverrides::default()).unwrap();
+
+        assert_eq!(meta, FacultyMeta { name: "розклад".to_owned(), speciality: None });
+    }
+
+    #[test]
+    fn json_does_not_depend_on_the_order_of_rows() {
+        let first = faculty(
+            "Понеділок;08:30-09:50;Мікроекономіка;1;1-13;1-225\n\
+             Понеділок;08:30-09:50;Алгебра;2;1-13;1-225\n\
+             Понеділок;08:30-09:50;Алгебра;1;1-13;1-225\n\
+             Вівторок;10:00-11:20;Алгебра;лекція;1-13;КМЦ\n",
+        );
+        let second = faculty(
+            "Вівторок;10:00-11:20;Алгебра;лекція;1-13;КМЦ\n\
+             Понеділок;08:30-09:50;Алгебра;1;1-13;1-225\n\
+             Понеділок;08:30-09:50;Алгебра;2;1-13;1-225\n\
+             Понеділок;08:30-09:50;Мікроекономіка;1;1-13;1-225\n",
+        );
+
+        assert_eq!(serde_json::to_string(&first).unwrap(), serde_json::to_string(&second).unwrap());
+    }
+
+    #[test]
+    fn sorts_disciplines_by_name_and_groups_by_day_time_and_name() {
+        let faculty = faculty(
+            "Середа;08:30-09:50;Мікроекономіка;1;1-13;1-225\n\
+             Вівторок;10:00-11:20;Алгебра;2;1-13;1-225\n\
+             Вівторок;10:00-11:20;Алгебра;1;1-13;1-225\n\
+             Вівторок;08:30-09:50;Алгебра;лекція;1-13;КМЦ\n\
+             Понеділок;11:40-13:00;Алгебра;3;1-13;1-225\n",
+        );
+        let disciplines = &faculty.specialities[&SpecialityName::Economics].disciplines;
+        let groups: Vec<String> = disciplines["Алгебра"].groups
+            .iter()
+            .map(|group| format!("{} {} {}", group.day, group.time, group.name))
+            .collect();
+
+        assert_eq!(disciplines.keys().collect::<Vec<_>>(), ["Алгебра", "Мікроекономіка"]);
+        assert_eq!(groups, [
+            "Понеділок 11:40-13:00 3",
+            "Вівторок 08:30-09:50 лекція",
+            "Вівторок 10:00-11:20 1",
+            "Вівторок 10:00-11:20 2",
+        ]);
+    }
+}
@@ -0,0 +1,431 @@
+use std::{
+    fmt::Display,
+    path::PathBuf,
+    str::FromStr,
+    time::{Duration, Instant, SystemTime},
+};
+
+use serde::Serialize;
+use serde_json::json;
+use tiny_http::{Header, Method, Request, Response};
+
+use crate::{
+    config::Config,
+    exam::Date,
+    group::{Day, LessonTime},
+    profile::Profile,
+    render,
+    schedule::*,
+    source::ScheduleSource,
+    spreadsheet::modification_times,
+    timetable::{DisciplineChoice, Selection, Timetable},
+    error::{ScheduleError, ScheduleResult},
+};
+
+/// How often the server checks the input files for changes, at most.
+const RELOAD_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Represents a local HTTP server exposing the schedule as a JSON API.
+///
+/// The parsed schedule is cached and parsed again only once any of the input files changes.
+///
+/// Endpoints (all `GET`):
+///
+/// * `/schedule` - the whole schedule, as in `schedule.json`
+/// * `/faculties` - faculty names
+/// * `/specialities?faculty=` - specialities of faculties
+/// * `/disciplines?faculty=&speciality=` - discipline names
+/// * `/lessons?faculty=&speciality=&week=&day=&discipline=&compact` - lessons matching the filters,
+///   `discipline` may be repeated in `<name part>[:<group>,<group>...]` format
+/// * `/free-rooms?day=&time=&week=` - rooms not occupied at the given time (e.g. `time=10:00-11:20`)
+/// * `/profiles/<name>.ics?semester_start=` - iCalendar feed of the profile `<name>.toml` (or `.json`)
+///   from the profiles directory
+pub struct Server {
+    /// The input files, patterns and directories with the metadata set explicitly.
+    source: ScheduleSource,
+    /// The user configuration.
+    config: Config,
+    /// The directory containing student profiles.
+    profiles: Option<PathBuf>,
+    /// The currently served schedule.
+    schedule: Schedule,
+    /// The input files with their modification times at the moment of the last parse.
+    modified: (Vec<PathBuf>, Vec<Option<SystemTime>>),
+    /// The moment the input files were last checked for changes.
+    checked: Instant,
+}
+
+/// Represents an HTTP response of the server.
+struct Reply {
+    /// The HTTP status code.
+    status: u16,
+    /// The value of the `Content-Type` header.
+    content_type: &'static str,
+    /// The response body.
+    body: String,
+}
+
+impl Reply {
+    /// Creates a successful JSON response, or an error response if the value cannot be serialized.
+    fn json<T: Serialize>(value: &T) -> Self {
+        match serde_json::to_string(value) {
+            Ok(body) => Reply {
+                status: 200,
+                content_type: "application/json; charset=utf-8",
+                body,
+            },
+            Err(e) => Reply::failure(500, &ScheduleError::from(e)),
+        }
+    }
+
+    /// Creates an error response with a JSON body.
+    fn error(status: u16, message: impl Display) -> Self {
+        Reply {
+            status,
+            content_type: "application/json; charset=utf-8",
+            body: json!({ "error": message.to_string() }).to_string(),
+        }
+    }
+
+    /// Creates an error response of a schedule error with a JSON body,
+    /// including the machine-readable error code.
+    fn failure(status: u16, error: &ScheduleError) -> Self {
+        Reply {
+            status,
+            content_type: "application/json; charset=utf-8",
+            body: json!({ "error": error.describe(), "code": error.code() }).to_string(),
+        }
+    }
+}
+
+impl Server {
+    /// Creates a new `Server` serving an already parsed schedule,
+    /// which is parsed again from the input files once they change.
+    ///
+    /// # Arguments
+    ///
+    /// * `schedule`: The schedule parsed from the input files.
+    /// * `source`: The input files, patterns and directories with the metadata set explicitly.
+    /// * `config`: The user configuration.
+    /// * `profiles`: The directory containing student profiles, if any.
+    pub fn with_schedule(schedule: Schedule, source: ScheduleSource, config: Config, profiles: Option<PathBuf>) -> Self {
+        let files = source.resolve().unwrap_or_default();
+        let modified = (files.clone(), modification_times(&files));
+
+        Server { source, config, profiles, schedule, modified, checked: Instant::now() }
+    }
+
+    /// Returns the currently served schedule.
+    pub fn schedule(&self) -> &Schedule {
+        &self.schedule
+    }
+
+    /// Listens for HTTP requests on the address and answers them until the process is stopped.
+    ///
+    /// # Arguments
+    ///
+    /// * `address`: The address to listen on (e.g. `127.0.0.1:8080`).
+    pub fn run(&mut self, address: &str) -> ScheduleResult<()> {
+        let server = tiny_http::Server::http(address)
+            .map_err(|e| ScheduleError::IoError(std::io::Error::other(e)))?;
+
+        for request in server.incoming_requests() {
+            if self.checked.elapsed() >= RELOAD_INTERVAL {
+                self.reload_if_changed();
+            }
+
+            self.respond(request);
+        }
+
+        Ok(())
+    }
+
+    /// Parses the schedule again if any input file has changed, or files were added or removed,
+    /// since the last parse. The previous schedule is kept if the new one cannot be parsed.
+    pub fn reload_if_changed(&mut self) {
+        self.checked = Instant::now();
+
+        let files = match self.source.resolve() {
+            Ok(files) => files,
+            Err(e) => return eprintln!("Cannot find the input files, keeping the previous schedule: {}", e.describe()),
+        };
+        let modified = (files.clone(), modification_times(&files));
+
+        if modified == self.modified {
+            return;
+        }
+
+        self.modified = modified;
+
+        match self.source.load_files(&files, &self.config) {
+            Ok(loaded) => {
+                for (path, e) in &loaded.skipped {
+                    eprintln!("Skipping `{}`: {}", path.display(), e.describe());
+                }
+
+                self.schedule = loaded.schedule;
+                eprintln!("Schedule reloaded");
+            },
+            Err(e) => eprintln!("Cannot reload the schedule, keeping the previous one: {}", e.describe()),
+        }
+    }
+
+    /// Answers a single request.
+    fn respond(&self, request: Request) {
+        let reply = if *request.method() == Method::Get {
+            self.handle(request.url())
+        } else {
+            Reply::error(405, "Only GET requests are supported")
+        };
+
+        let header = Header::from_bytes("Content-Type", reply.content_type)
+            .expect("static header is valid");
+        let response = Response::from_string(reply.body)
+            .with_status_code(reply.status)
+            .with_header(header);
+
+        if let Err(e) = request.respond(response) {
+            eprintln!("Cannot send a response: {e}");
+        }
+    }
+
+    /// Routes a request URL to its endpoint.
+    fn handle(&self, url: &str) -> Reply {
+        let (path, query) = parse_url(url);
+        let param = |name: &str| query.iter().find(|(key, _)| key == name).map(|(_, value)| value.as_str());
+
+        let result = match path.as_str() {
+            "/schedule" => Ok(Reply::json(&self.schedule)),
+            "/faculties" => Ok(Reply::json(&self.schedule.faculties.iter().map(|f| &f.name).collect::<Vec<_>>())),
+            "/specialities" => self.specialities(param("faculty")),
+            "/disciplines" => self.disciplines(param("faculty"), param("speciality")),
+            "/lessons" => self.lessons(&query),
+            "/free-rooms" => self.free_rooms(param("day"), param("time"), param("week")),
+            _ => match path.strip_prefix("/profiles/").and_then(|name| name.strip_suffix(".ics")) {
+                Some(name) => self.profile_feed(name, param("semester_start")),
+                None => Err(Reply::error(404, format!("No such endpoint: `{path}`"))),
+            },
+        };
+
+        result.unwrap_or_else(|reply| reply)
+    }
+
+    /// Lists specialities of the faculties.
+    fn specialities(&self, faculty: Option<&str>) -> Result<Reply, Reply> {
+        let specialities: Vec<_> = self.schedule.faculties
+            .iter()
+            .filter(|f| faculty.is_none_or(|name| f.name == name))
+            .map(|f| json!({ "Факультет": f.name, "Спеціальності": f.specialities.keys().collect::<Vec<_>>() }))
+            .collect();
+
+        Ok(Reply::json(&specialities))
+    }
+
+    /// Lists discipline names of the faculty and speciality.
+    fn disciplines(&self, faculty: Option<&str>, speciality: Option<&str>) -> Result<Reply, Reply> {
+        let speciality = speciality.map(parse_param::<SpecialityName>).transpose()?;
+
+        let mut disciplines: Vec<&String> = self.schedule.faculties
+            .iter()
+            .filter(|f| faculty.is_none_or(|name| f.name == name))
+            .flat_map(|f| &f.specialities)
+            .filter(|(name, _)| speciality.is_none_or(|s| **name == s))
+            .flat_map(|(_, s)| s.disciplines.keys())
+            .collect();
+        disciplines.sort_unstable();
+        disciplines.dedup();
+
+        Ok(Reply::json(&disciplines))
+    }
+
+    /// Lists lessons matching the query filters.
+    fn lessons(&self, query: &[(String, String)]) -> Result<Reply, Reply> {
+        let mut selection = Selection::default();
+        let mut day = None;
+        let mut compact = false;
+
+        for (key, value) in query {
+            match key.as_str() {
+                "faculty" => selection.faculty = Some(value.clone()),
+                "speciality" => selection.speciality = Some(parse_param(value)?),
+                "week" => selection.week = Some(parse_param(value)?),
+                "discipline" => selection.disciplines.push(parse_param::<DisciplineChoice>(value)?),
+                "day" => day = Some(parse_param::<Day>(value)?),
+                "compact" => compact = true,
+                _ => Err(Reply::error(400, format!("Unknown parameter: `{key}`")))?,
+            }
+        }
+
+        let timetable = if compact {
+            Timetable::compact(&self.schedule, &selection, &self.config.campus)
+        } else {
+            Timetable::new(&self.schedule, &selection)
+        };
+
+        let lessons: Vec<_> = timetable.lessons
+            .iter()
+            .filter(|lesson| day.is_none_or(|day| lesson.group.day == day))
+            .collect();
+
+        Ok(Reply::json(&lessons))
+    }
+
+    /// Lists rooms which are free at the given time.
+    fn free_rooms(&self, day: Option<&str>, time: Option<&str>, week: Option<&str>) -> Result<Reply, Reply> {
+        let day = parse_param::<Day>(day.ok_or_else(|| Reply::error(400, "The `day` parameter is required"))?)?;
+        let time = parse_param::<LessonTime>(time.ok_or_else(|| Reply::error(400, "The `time` parameter is required"))?)?;
+        let week = week.map(parse_param::<u8>).transpose()?;
+
+        let rooms: Vec<String> = self.schedule
+            .free_rooms(day, time, week)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+
+        Ok(Reply::json(&rooms))
+    }
+
+    /// Renders the iCalendar feed of a student profile from the profiles directory.
+    fn profile_feed(&self, name: &str, semester_start: Option<&str>) -> Result<Reply, Reply> {
+        let directory = self.profiles
+            .as_ref()
+            .ok_or_else(|| Reply::error(404, "No profiles directory is configured"))?;
+
+        if name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.') {
+            return Err(Reply::error(400, format!("Invalid profile name: `{name}`")));
+        }
+
+        let path = ["toml", "json"]
+            .iter()
+            .map(|extension| directory.join(format!("{name}.{extension}")))
+            .find(|path| path.is_file())
+            .ok_or_else(|| Reply::error(404, format!("No such profile: `{name}`")))?;
+
+        let profile = Profile::from_path(&path).map_err(|e| Reply::failure(500, &e))?;
+        profile.validate(&self.schedule).map_err(|e| Reply::failure(422, &e))?;
+
+        let semester_start = match semester_start {
+            Some(date) => parse_param::<Date>(date)?,
+            None => profile.semester_start
+                .ok_or_else(|| Reply::error(400, "The semester start is unknown: pass `semester_start` or set it in the profile"))?,
+        };
+
+        let timetable = Timetable::new(&self.schedule, &profile.selection());
+
+        Ok(Reply {
+            status: 200,
+            content_type: "text/calendar; charset=utf-8",
+            body: render::ics::render_lessons(&timetable, semester_start, name),
+        })
+    }
+}
+
+/// Parses a query parameter, answering with `400 Bad Request` on failure.
+fn parse_param<T: FromStr>(value: &str) -> Result<T, Reply>
+where
+    T::Err: Display,
+{
+    value.parse::<T>().map_err(|e| Reply::error(400, format!("Invalid parameter `{value}`: {e}")))
+}
+
+/// Splits a request URL into the decoded path and query parameters.
+fn parse_url(url: &str) -> (String, Vec<(String, String)>) {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+
+    let parameters = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (decode(key), decode(value))
+        })
+        .collect();
+
+    (decode(path), parameters)
+}
+
+/// Decodes a percent-encoded URL component, treating `+` as a space.
+fn decode(s: &str) -> String {
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut input = s.bytes();
+
+    while let Some(byte) = input.next() {
+        match byte {
+            b'+' => bytes.push(b' '),
+            b'%' => {
+                let hex: Vec<u8> = input.by_ref().take(2).collect();
+                let decoded = std::str::from_utf8(&hex)
+                    .ok()
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+                match decoded {
+                    Some(decoded) => bytes.push(decoded),
+                    None => {
+                        bytes.push(b'%');
+                        bytes.extend(hex);
+                    },
+                }
+            },
+            _ => bytes.push(byte),
+        }
+    }
+
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::writer::HEADERS;
+
+    /// Writes a single faculty spreadsheet with the rows into a temporary directory
+    /// and returns a server serving it.
+    fn server(test: &str, rows: &str) -> (Server, PathBuf) {
+        let dir = std::env::temp_dir().join(format!("naukma_schedule_{}_{test}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        let path = dir.join("ФІ.Економіка.csv");
+        fs::write(&path, format!("{}\n{rows}", HEADERS.join(";"))).unwrap();
+
+        let source = ScheduleSource { files: vec![path.clone()], ..Default::default() };
+        let config = Config::default();
+        let schedule = source.load(&config).unwrap().schedule;
+
+        (Server::with_schedule(schedule, source, config, None), path)
+    }
+
+    /// Serializes the served schedule to compare it between reloads.
+    fn served(server: &Server) -> String {
+        serde_json::to_string(server.schedule()).unwrap()
+    }
+
+    #[test]
+    fn schedule_is_not_parsed_again_while_files_are_unchanged() {
+        let (mut server, path) = server("server_unchanged", "Понеділок;08:30-09:50;Алгебра;лекція;1-13;КМЦ\n");
+        let before = served(&server);
+        let modified = fs::metadata(&path).unwrap().modified().unwrap();
+
+        fs::write(&path, format!("{}\nВівторок;10:00-11:20;Алгебра;1;1-13;3-205\n", HEADERS.join(";"))).unwrap();
+        fs::File::options().write(true).open(&path).unwrap().set_modified(modified).unwrap();
+        server.reload_if_changed();
+
+        assert_eq!(served(&server), before);
+    }
+
+    #[test]
+    fn schedule_is_parsed_again_once_files_change() {
+        let (mut server, path) = server("server_changed", "Понеділок;08:30-09:50;Алгебра;лекція;1-13;КМЦ\n");
+        let before = served(&server);
+        let modified = fs::metadata(&path).unwrap().modified().unwrap();
+
+        fs::write(&path, format!("{}\nВівторок;10:00-11:20;Алгебра;1;1-13;3-205\n", HEADERS.join(";"))).unwrap();
+        fs::File::options().write(true).open(&path).unwrap().set_modified(modified + Duration::from_secs(1)).unwrap();
+        server.reload_if_changed();
+
+        assert_ne!(served(&server), before);
+        assert!(served(&server).contains("Вівторок"));
+    }
+}
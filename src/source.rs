@@ -0,0 +1,192 @@
+use std::path::{Path, PathBuf};
+
+use crate::{
+    config::Config,
+    metadata::Metadata,
+    schedule::{MetaOverrides, Schedule},
+    spreadsheet::{discover_spreadsheets, expand_pattern},
+    error::{ScheduleError, ScheduleResult},
+};
+
+/// Represents the inputs of a schedule: spreadsheet files, glob patterns and directories,
+/// with the metadata set explicitly (e.g. with command-line flags).
+///
+/// The command-line tool, its watch mode and the server parse schedules with it,
+/// so a reloaded schedule is the same as a freshly parsed one.
+#[derive(Default, Clone, Debug)]
+pub struct ScheduleSource {
+    /// Paths to spreadsheets or JSON schedules, or glob patterns matching them (e.g. `2024-1/**/*.xlsx`).
+    pub files: Vec<PathBuf>,
+    /// Directories to search for spreadsheets recursively.
+    pub dirs: Vec<PathBuf>,
+    /// The faculty metadata set for every parsed faculty.
+    pub overrides: MetaOverrides,
+    /// The schedule metadata taking precedence over the configuration and the detected metadata.
+    pub metadata: Metadata,
+    /// Whether the files which cannot be parsed are skipped instead of failing.
+    pub lenient: bool,
+    /// Whether duplicate rows of disciplines are merged, in addition to the configuration option.
+    pub merge_duplicates: bool,
+    /// Whether the generation time is written, in addition to the configuration option.
+    pub timestamp: bool,
+}
+
+/// Represents a schedule parsed from the inputs, with the outcome of every file.
+#[derive(Debug)]
+pub struct Loaded {
+    /// The schedule of every parsed file.
+    pub schedule: Schedule,
+    /// Parsed files with the faculties and exam sessions found in them.
+    pub parsed: Vec<(PathBuf, String)>,
+    /// Files skipped in lenient mode with their errors.
+    pub skipped: Vec<(PathBuf, ScheduleError)>,
+}
+
+impl ScheduleSource {
+    /// Checks whether the input files are discovered in directories or with glob patterns,
+    /// so the list of files may change between parses.
+    pub fn is_discovered(&self) -> bool {
+        !self.dirs.is_empty() || self.files.iter().any(|path| is_pattern(path))
+    }
+
+    /// Resolves the input files: glob patterns are expanded and directories are searched recursively.
+    /// Files found several times are included once.
+    ///
+    /// # Returns
+    ///
+    /// A `ScheduleResult` containing the paths of the input files, or an error
+    /// if a pattern is invalid or a directory cannot be read.
+    pub fn resolve(&self) -> ScheduleResult<Vec<PathBuf>> {
+        let mut inputs: Vec<PathBuf> = vec![];
+
+        for path in &self.files {
+            if is_pattern(path) {
+                inputs.extend(expand_pattern(&path.to_string_lossy())?);
+            } else {
+                inputs.push(path.clone());
+            }
+        }
+
+        for dir in &self.dirs {
+            inputs.extend(discover_spreadsheets(dir).map_err(|e| ScheduleError::in_file(dir, e))?);
+        }
+
+        let mut seen = std::collections::HashSet::new();
+        inputs.retain(|path| seen.insert(path.clone()));
+
+        Ok(inputs)
+    }
+
+    /// Resolves the input files and parses them.
+    ///
+    /// # Arguments
+    ///
+    /// * `config`: The configuration with the schedule metadata.
+    ///
+    /// # Returns
+    ///
+    /// A `ScheduleResult` containing the parsed schedule, or the error of the first file
+    /// which cannot be parsed (unless in lenient mode).
+    pub fn load(&self, config: &Config) -> ScheduleResult<Loaded> {
+        self.load_files(&self.resolve()?, config)
+    }
+
+    /// Parses the already resolved input files concurrently.
+    ///
+    /// Files which cannot be parsed are skipped in lenient mode. The metadata set explicitly
+    /// takes precedence over the configuration, which takes precedence over file names and title rows.
+    /// Duplicate rows are merged and the generation time is set if requested here or in the configuration
+    /// (the time is also set if `SOURCE_DATE_EPOCH` is).
+    ///
+    /// # Arguments
+    ///
+    /// * `files`: The input files, as resolved with `ScheduleSource::resolve`.
+    /// * `config`: The configuration with the schedule metadata and the semester length.
+    ///
+    /// # Returns
+    ///
+    /// A `ScheduleResult` containing the parsed schedule, or the error of the first file
+    /// which cannot be parsed (unless in lenient mode).
+    pub fn load_files(&self, files: &[PathBuf], config: &Config) -> ScheduleResult<Loaded> {
+        let mut loaded = Loaded { schedule: Schedule::default(), parsed: vec![], skipped: vec![] };
+
+        for (path, parsed) in files.iter().zip(Schedule::parse_each(files, &self.overrides, config.semester_weeks)) {
+            match parsed {
+                Ok(parsed) => {
+                    loaded.parsed.push((path.clone(), parsed.describe()));
+                    loaded.schedule.merge(parsed);
+                },
+                Err(e) if self.lenient => loaded.skipped.push((path.clone(), e)),
+                Err(e) => return Err(ScheduleError::in_file(path, e)),
+            }
+        }
+
+        if self.merge_duplicates || config.merge_duplicates {
+            for faculty in &mut loaded.schedule.faculties {
+                faculty.merge_duplicates();
+            }
+        }
+
+        let detected = std::mem::take(&mut loaded.schedule.metadata);
+        loaded.schedule.metadata = self.metadata.clone().or(config.metadata.clone()).or(detected);
+        loaded.schedule.metadata.stamp(self.timestamp || config.timestamp);
+
+        Ok(loaded)
+    }
+}
+
+/// Checks whether an input path is a glob pattern. Existing files are taken literally,
+/// so file names may contain pattern characters (e.g. `Розклад [весна].xlsx`).
+fn is_pattern(path: &Path) -> bool {
+    !path.exists() && path.to_string_lossy().contains(['*', '?', '['])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::source_date_epoch;
+
+    /// Writes a schedule generated at a fixed time as JSON into a temporary directory.
+    fn stamped_schedule(test: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("naukma_schedule_{}_{test}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let mut schedule = Schedule::default();
+        schedule.metadata.generated_at = Some("2020-01-01T00:00:00Z".to_owned());
+
+        let path = dir.join("schedule.json");
+        std::fs::write(&path, serde_json::to_string(&schedule).unwrap()).unwrap();
+        path
+    }
+
+    #[test]
+    fn file_names_with_pattern_characters_are_taken_literally() {
+        let dir = std::env::temp_dir().join(format!("naukma_schedule_{}_literal", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("Розклад [весна].csv");
+        std::fs::write(&path, "").unwrap();
+
+        let source = ScheduleSource { files: vec![path.clone()], ..Default::default() };
+
+        assert!(!source.is_discovered());
+        assert_eq!(source.resolve().unwrap(), [path]);
+    }
+
+    #[test]
+    fn generation_time_of_parsed_schedules_is_not_kept() {
+        let path = stamped_schedule("not_kept");
+        let loaded = ScheduleSource::default().load_files(&[path], &Config::default()).unwrap();
+        let epoch = source_date_epoch().map(|time| time.format("%Y-%m-%dT%H:%M:%SZ").to_string());
+
+        assert_eq!(loaded.schedule.metadata.generated_at, epoch);
+    }
+
+    #[test]
+    fn generation_time_is_set_on_request() {
+        let path = stamped_schedule("on_request");
+        let source = ScheduleSource { timestamp: true, ..Default::default() };
+        let loaded = source.load_files(&[path], &Config::default()).unwrap();
+
+        assert!(loaded.schedule.metadata.generated_at.is_some());
+    }
+}
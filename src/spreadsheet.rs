@@ -0,0 +1,680 @@
+use std::fs::File;
+use std::io::{self, Read, Seek, Cursor};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use std::collections::{HashMap, HashSet};
+use calamine::{open_workbook_auto, open_workbook_auto_from_rs, Data, Dimensions, OdsError, Range, Reader, Sheets};
+
+use crate::{
+    schedule::Faculty,
+    error::{ScheduleError, ScheduleResult},
+};
+
+/// Minimal number of columns a lesson row consists of.
+pub const COLUMNS: usize = 6;
+
+/// Represents the format of a schedule spreadsheet file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpreadsheetFormat {
+    /// A workbook supported by calamine: `.xlsx`, `.xls`, `.xlsb` or `.ods`.
+    Workbook,
+    /// A comma- or semicolon-separated values text file.
+    Csv,
+}
+
+impl SpreadsheetFormat {
+    /// Detects the format of a spreadsheet file by its extension or,
+    /// if the extension is unknown, by the magic bytes of its content.
+    ///
+    /// # Arguments
+    ///
+    /// * `path`: The path to the spreadsheet file.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the detected format, or an error if the file cannot be read.
+    pub fn detect(path: &Path) -> ScheduleResult<Self> {
+        match Self::from_extension(path) {
+            Some(format) => Ok(format),
+            None => {
+                let mut magic = [0u8; 4];
+                let read = File::open(path)?.read(&mut magic)?;
+
+                Ok(Self::from_magic(&magic[..read]))
+            }
+        }
+    }
+
+    /// Detects the format of a spreadsheet file by its extension only.
+    ///
+    /// # Returns
+    ///
+    /// The format, or `None` if the extension is not a known spreadsheet extension.
+    pub fn from_extension(path: &Path) -> Option<Self> {
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_lowercase);
+
+        match extension.as_deref() {
+            Some("xlsx" | "xlsm" | "xlsb" | "xls" | "ods") => Some(SpreadsheetFormat::Workbook),
+            Some("csv") => Some(SpreadsheetFormat::Csv),
+            _ => None,
+        }
+    }
+
+    /// Detects the format of a spreadsheet by the first bytes of its content.
+    ///
+    /// Zip archives (`.xlsx`, `.ods`) and OLE compound documents (`.xls`)
+    /// are workbooks, anything else is treated as text.
+    pub fn from_magic(magic: &[u8]) -> Self {
+        const ZIP: &[u8] = b"PK\x03\x04";
+        const OLE: &[u8] = &[0xD0, 0xCF, 0x11, 0xE0];
+
+        if magic.starts_with(ZIP) || magic.starts_with(OLE) {
+            SpreadsheetFormat::Workbook
+        } else {
+            SpreadsheetFormat::Csv
+        }
+    }
+}
+
+/// Reads all worksheets of a spreadsheet file of any supported format.
+///
+/// CSV files consist of a single worksheet named `Аркуш1`, like the
+/// spreadsheets published by the university.
+///
+/// # Arguments
+///
+/// * `path`: The path to the spreadsheet file.
+///
+/// # Returns
+///
+/// A `Result` containing worksheet names with their cells, or an error if reading fails.
+pub fn read_worksheets(path: &Path) -> ScheduleResult<Vec<(String, Range<Data>)>> {
+    match SpreadsheetFormat::detect(path)? {
+        SpreadsheetFormat::Workbook => {
+            let mut workbook = open_workbook_auto(path)?;
+
+            read_workbook(&mut workbook, || std::fs::read(path))
+        },
+        SpreadsheetFormat::Csv => {
+            let mut content = String::new();
+            File::open(path)?.read_to_string(&mut content)?;
+
+            Ok(vec![(Faculty::LEGACY_SHEET.to_owned(), read_csv(&content)?)])
+        },
+    }
+}
+
+/// Reads all worksheets of a spreadsheet of any supported format from a reader.
+///
+/// The format is detected by the magic bytes of the content.
+///
+/// # Arguments
+///
+/// * `reader`: The source of the spreadsheet content.
+///
+/// # Returns
+///
+/// A `Result` containing worksheet names with their cells, or an error if reading fails.
+pub fn read_worksheets_from<R: Read + Seek>(mut reader: R) -> ScheduleResult<Vec<(String, Range<Data>)>> {
+    let mut content = vec![];
+    reader.rewind()?;
+    reader.read_to_end(&mut content)?;
+
+    match SpreadsheetFormat::from_magic(&content) {
+        SpreadsheetFormat::Workbook => {
+            let mut workbook = open_workbook_auto_from_rs(Cursor::new(content.as_slice()))?;
+
+            read_workbook(&mut workbook, || Ok(content.clone()))
+        },
+        SpreadsheetFormat::Csv => {
+            let content = String::from_utf8(content)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+            Ok(vec![(Faculty::LEGACY_SHEET.to_owned(), read_csv(&content)?)])
+        },
+    }
+}
+
+/// Reads all worksheets of an opened workbook, resolving their merged cells.
+///
+/// The content of the workbook file is read again for `.ods` workbooks only,
+/// as calamine does not provide their merged regions.
+fn read_workbook<RS: Read + Seek>(
+    workbook: &mut Sheets<RS>,
+    content: impl FnOnce() -> io::Result<Vec<u8>>,
+) -> ScheduleResult<Vec<(String, Range<Data>)>> {
+    let mut ods_regions = HashMap::new();
+
+    match workbook {
+        Sheets::Xlsx(xlsx) => xlsx.load_merged_regions().map_err(calamine::Error::Xlsx)?,
+        Sheets::Ods(_) => ods_regions = ods_merged_regions(&content()?)?,
+        _ => {},
+    }
+
+    let worksheets = workbook.worksheets();
+
+    Ok(worksheets
+        .into_iter()
+        .map(|(name, range)| {
+            let regions = match workbook {
+                Sheets::Xlsx(xlsx) => xlsx
+                    .merged_regions_by_sheet(&name)
+                    .into_iter()
+                    .map(|(_, _, dimensions)| *dimensions)
+                    .collect(),
+                Sheets::Xls(xls) => xls.worksheet_merge_cells(&name).unwrap_or_default(),
+                _ => ods_regions.remove(&name).unwrap_or_default(),
+            };
+
+            (name, unmerge(range, &regions))
+        })
+        .collect())
+}
+
+/// Reads the merged regions of every sheet of an `.ods` workbook.
+///
+/// # Arguments
+///
+/// * `content`: The content of the `.ods` file.
+///
+/// # Returns
+///
+/// A `Result` containing the merged regions by sheet name, or an error if the file is not a valid `.ods` archive.
+fn ods_merged_regions(content: &[u8]) -> ScheduleResult<HashMap<String, Vec<Dimensions>>> {
+    let ods_error = |e: OdsError| ScheduleError::from(calamine::Error::Ods(e));
+
+    let mut archive = zip::ZipArchive::new(Cursor::new(content)).map_err(|e| ods_error(OdsError::Zip(e)))?;
+    let mut xml = vec![];
+    archive
+        .by_name("content.xml")
+        .map_err(|e| ods_error(OdsError::Zip(e)))?
+        .read_to_end(&mut xml)?;
+
+    ods_merged_cells(&xml).map_err(|e| ods_error(OdsError::Xml(e)))
+}
+
+/// Finds the cells of an `.ods` `content.xml` spanning several columns or rows
+/// (`table:number-columns-spanned`, `table:number-rows-spanned`), counting repeated rows and cells.
+fn ods_merged_cells(xml: &[u8]) -> Result<HashMap<String, Vec<Dimensions>>, quick_xml::Error> {
+    use quick_xml::events::{BytesStart, Event};
+
+    /// Reads a numeric attribute of an element, defaulting to 1.
+    fn number(element: &BytesStart, name: &str) -> u32 {
+        element
+            .try_get_attribute(name)
+            .ok()
+            .flatten()
+            .and_then(|attribute| std::str::from_utf8(&attribute.value).ok()?.parse().ok())
+            .unwrap_or(1)
+    }
+
+    let mut reader = quick_xml::Reader::from_reader(xml);
+    let mut buffer = vec![];
+    let mut regions: HashMap<String, Vec<Dimensions>> = HashMap::new();
+    let mut sheet = String::new();
+    let (mut row, mut column, mut repeated_rows) = (0, 0, 1);
+
+    loop {
+        let (element, is_empty) = match reader.read_event_into(&mut buffer)? {
+            Event::Start(element) => (element, false),
+            Event::Empty(element) => (element, true),
+            Event::End(element) => {
+                match element.name().as_ref() {
+                    b"table:table-row" => row += repeated_rows,
+                    b"table:table" => row = 0,
+                    _ => {},
+                }
+
+                buffer.clear();
+                continue;
+            },
+            Event::Eof => break,
+            _ => {
+                buffer.clear();
+                continue;
+            },
+        };
+
+        match element.name().as_ref() {
+            b"table:table" => {
+                sheet = element
+                    .try_get_attribute("table:name")
+                    .ok()
+                    .flatten()
+                    .and_then(|name| name.decode_and_unescape_value(reader.decoder()).ok().map(|name| name.into_owned()))
+                    .unwrap_or_default();
+                row = 0;
+            },
+            b"table:table-row" => {
+                column = 0;
+                repeated_rows = number(&element, "table:number-rows-repeated");
+
+                if is_empty {
+                    row += repeated_rows;
+                }
+            },
+            b"table:table-cell" | b"table:covered-table-cell" => {
+                let columns = number(&element, "table:number-columns-spanned");
+                let rows = number(&element, "table:number-rows-spanned");
+
+                if columns > 1 || rows > 1 {
+                    regions.entry(sheet.clone()).or_default().push(Dimensions {
+                        start: (row, column),
+                        end: (row + rows - 1, column + columns - 1),
+                    });
+                }
+
+                column += number(&element, "table:number-columns-repeated");
+            },
+            _ => {},
+        }
+
+        buffer.clear();
+    }
+
+    Ok(regions)
+}
+
+/// Resolves merged cells of a worksheet, which spreadsheet readers return as a value
+/// in the top-left cell followed by empty cells.
+///
+/// Every cell of a merged region from the header row down gets the value of the region,
+/// e.g. a discipline merged over several group rows. Header cells merged horizontally span
+/// several physical columns of one logical column: the extra columns are removed only
+/// if they have no own values below the header. Otherwise they are kept, and matching
+/// the header is left to the caller.
+///
+/// # Arguments
+///
+/// * `range`: The cells of the worksheet.
+/// * `regions`: The merged regions of the worksheet.
+///
+/// # Returns
+///
+/// The cells of the worksheet with the values of merged regions in every cell.
+pub fn unmerge(mut range: Range<Data>, regions: &[Dimensions]) -> Range<Data> {
+    let (Some(start), Some(end)) = (range.start(), range.end()) else {
+        return range;
+    };
+
+    let header = header_row(&range).map(|index| start.0 + index as u32);
+    let first_row = header.unwrap_or(start.0);
+    let is_empty = |range: &Range<Data>, cell: (u32, u32)| range.get_value(cell).is_none_or(|value| *value == Data::Empty);
+
+    // Extra columns of merged header cells, removable if every cell below the header
+    // is empty or continues a region merged from the columns to the left
+    let mut extra_columns: Vec<u32> = regions
+        .iter()
+        .filter(|region| header.is_some_and(|header| region.start.0 <= header && header <= region.end.0))
+        .flat_map(|region| region.start.1 + 1..=region.end.1)
+        .collect();
+
+    extra_columns.retain(|column| {
+        (first_row + 1..=end.0).all(|row| {
+            let continued = regions.iter().any(|region| {
+                region.start.1 < *column && *column <= region.end.1 && region.start.0 <= row && row <= region.end.0
+            });
+
+            continued || is_empty(&range, (row, *column))
+        })
+    });
+
+    for region in regions {
+        let value = match range.get_value(region.start) {
+            Some(value) if *value != Data::Empty => value.clone(),
+            _ => continue,
+        };
+
+        for row in region.start.0.max(first_row)..=region.end.0.min(end.0) {
+            for column in region.start.1..=region.end.1.min(end.1) {
+                if is_empty(&range, (row, column)) {
+                    range.set_value((row, column), value.clone());
+                }
+            }
+        }
+    }
+
+    if extra_columns.is_empty() {
+        return range;
+    }
+
+    let width = (start.1..=end.1).filter(|column| !extra_columns.contains(column)).count() as u32;
+    let mut unmerged = Range::new(start, (end.0, start.1 + width.max(1) - 1));
+
+    for (row, column, value) in range.used_cells() {
+        let (row, column) = (start.0 + row as u32, start.1 + column as u32);
+
+        if !extra_columns.contains(&column) {
+            let shift = extra_columns.iter().filter(|extra| **extra < column).count() as u32;
+            unmerged.set_value((row, column - shift), value.clone());
+        }
+    }
+
+    unmerged
+}
+
+/// Checks whether a discovered file is a schedule spreadsheet: it must have a spreadsheet
+/// extension and must not be a lock file of an office application (e.g. `~$Факультет.xlsx`).
+pub fn is_spreadsheet(path: &Path) -> bool {
+    let is_lock = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with("~$") || name.starts_with(".~lock."));
+
+    !is_lock && path.is_file() && SpreadsheetFormat::from_extension(path).is_some()
+}
+
+/// Finds every spreadsheet in a directory and its subdirectories.
+///
+/// Symbolic links to directories are followed once, so link cycles do not recurse forever.
+///
+/// # Arguments
+///
+/// * `dir`: The directory to search in.
+///
+/// # Returns
+///
+/// A `Result` containing paths of the found spreadsheets in ascending order,
+/// or an error if a directory cannot be read.
+pub fn discover_spreadsheets(dir: &Path) -> ScheduleResult<Vec<PathBuf>> {
+    let mut found = vec![];
+    let mut dirs = vec![dir.to_path_buf()];
+    let mut visited = HashSet::new();
+
+    while let Some(dir) = dirs.pop() {
+        if !visited.insert(dir.canonicalize()?) {
+            continue;
+        }
+
+        for entry in std::fs::read_dir(&dir)? {
+            let path = entry?.path();
+
+            if path.is_dir() {
+                dirs.push(path);
+            } else if is_spreadsheet(&path) {
+                found.push(path);
+            }
+        }
+    }
+
+    found.sort();
+    Ok(found)
+}
+
+/// Finds the spreadsheets matching a glob pattern (e.g. `2024-1/**/*.xlsx`).
+///
+/// # Arguments
+///
+/// * `pattern`: The glob pattern.
+///
+/// # Returns
+///
+/// A `Result` containing paths of the matching spreadsheets in ascending order,
+/// or an error if the pattern is invalid.
+pub fn expand_pattern(pattern: &str) -> ScheduleResult<Vec<PathBuf>> {
+    let paths = glob::glob(pattern)
+        .map_err(|e| ScheduleError::InvalidPattern(format!("{pattern}: {e}")))?;
+
+    let mut found: Vec<PathBuf> = paths
+        .filter_map(Result::ok)
+        .filter(|path| is_spreadsheet(path))
+        .collect();
+
+    found.sort();
+    Ok(found)
+}
+
+/// Returns the modification times of the files, or `None` for missing files,
+/// so that changed spreadsheets can be detected by comparing the results.
+pub fn modification_times<P: AsRef<Path>>(paths: &[P]) -> Vec<Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|path| path.as_ref().metadata().and_then(|m| m.modified()).ok())
+        .collect()
+}
+
+/// First cells of header rows: `День` of weekly schedules and `Дата` of exam sessions.
+pub const HEADER_CELLS: [&str; 2] = ["День", "Дата"];
+
+/// Finds the index of the header row (the one starting with `День` or `Дата`) of a worksheet.
+pub fn header_row(range: &Range<Data>) -> Option<usize> {
+    range.rows().position(|row| {
+        matches!(row.first(), Some(Data::String(s)) if HEADER_CELLS.contains(&s.trim()))
+    })
+}
+
+/// Collects the non-empty text cells of the title rows above the header of a worksheet.
+pub fn title_rows(range: &Range<Data>) -> Vec<String> {
+    let header = header_row(range).unwrap_or(0);
+
+    range.rows()
+        .take(header)
+        .flatten()
+        .filter_map(|cell| match cell {
+            Data::String(s) if !s.trim().is_empty() => Some(s.trim().to_owned()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Reads CSV content into a worksheet range.
+///
+/// The delimiter (`,` or `;`) is guessed from the header line (or the first line,
+/// if there is no header). Empty fields become empty cells, so the rows
+/// are processed exactly like spreadsheet ones.
+pub fn read_csv(content: &str) -> ScheduleResult<Range<Data>> {
+    let header_line = content
+        .lines()
+        .find(|line| {
+            let line = line.trim_start_matches(['"', ' ']);
+            HEADER_CELLS.iter().any(|header| line.starts_with(header))
+        })
+        .or_else(|| content.lines().next())
+        .unwrap_or_default();
+    let delimiter = if header_line.matches(';').count() > header_line.matches(',').count() {
+        b';'
+    } else {
+        b','
+    };
+
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(content.as_bytes());
+
+    let mut rows = vec![];
+    for record in reader.records() {
+        rows.push(record?);
+    }
+
+    let width = rows.iter().map(|r| r.len()).max().unwrap_or(0).max(COLUMNS);
+    let height = rows.len().max(1);
+    let mut range = Range::new((0, 0), (height as u32 - 1, width as u32 - 1));
+
+    for (row, record) in rows.iter().enumerate() {
+        for (column, field) in record.iter().enumerate() {
+            if !field.trim().is_empty() {
+                range.set_value((row as u32, column as u32), Data::String(field.to_owned()));
+            }
+        }
+    }
+
+    Ok(range)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates a worksheet of text rows, leaving empty strings as empty cells.
+    fn sheet(rows: &[&[&str]]) -> Range<Data> {
+        let width = rows.iter().map(|row| row.len()).max().unwrap_or(1) as u32;
+        let mut range = Range::new((0, 0), (rows.len() as u32 - 1, width - 1));
+
+        for (row, cells) in rows.iter().enumerate() {
+            for (column, cell) in cells.iter().enumerate().filter(|(_, cell)| !cell.is_empty()) {
+                range.set_value((row as u32, column as u32), Data::String(cell.to_string()));
+            }
+        }
+
+        range
+    }
+
+    /// Creates a merged region from the first to the last cell, inclusive.
+    fn region(start: (u32, u32), end: (u32, u32)) -> Dimensions {
+        Dimensions { start, end }
+    }
+
+    /// Returns the text of a cell.
+    fn text(range: &Range<Data>, cell: (u32, u32)) -> String {
+        range.get_value(cell).map(ToString::to_string).unwrap_or_default()
+    }
+
+    #[test]
+    fn fills_every_cell_of_merged_regions() {
+        let range = sheet(&[
+            &["День", "Час", "Дисципліна", "Група", "Тижні", "Аудиторія"],
+            &["Понеділок", "08:30-09:50", "Алгебра", "1", "1-13", "1-225"],
+            &["", "", "", "2", "", ""],
+        ]);
+        let range = unmerge(range, &[region((1, 0), (2, 0)), region((1, 2), (2, 2)), region((1, 4), (2, 5))]);
+
+        assert_eq!(text(&range, (2, 0)), "Понеділок");
+        assert_eq!(text(&range, (2, 1)), "");
+        assert_eq!(text(&range, (2, 2)), "Алгебра");
+        assert_eq!(text(&range, (2, 3)), "2");
+        assert_eq!(text(&range, (1, 5)), "1-225");
+        assert_eq!(text(&range, (2, 4)), "1-13");
+        assert_eq!(text(&range, (2, 5)), "1-13");
+    }
+
+    #[test]
+    fn removes_empty_columns_of_merged_headers() {
+        let range = sheet(&[
+            &["День", "Час", "Дисципліна", "", "Група"],
+            &["Понеділок", "08:30-09:50", "Алгебра", "", "1"],
+        ]);
+        let range = unmerge(range, &[region((0, 2), (0, 3))]);
+
+        assert_eq!(range.width(), 4);
+        assert_eq!(text(&range, (1, 2)), "Алгебра");
+        assert_eq!(text(&range, (1, 3)), "1");
+    }
+
+    #[test]
+    fn keeps_columns_of_merged_headers_with_values() {
+        let range = sheet(&[
+            &["День", "Час", "Дисципліна", "", "Група"],
+            &["Понеділок", "08:30-09:50", "Алгебра", "доц. Петренко", "1"],
+        ]);
+        let range = unmerge(range, &[region((0, 2), (0, 3))]);
+
+        assert_eq!(range.width(), 5);
+        assert_eq!(text(&range, (1, 3)), "доц. Петренко");
+        assert_eq!(text(&range, (1, 4)), "1");
+    }
+
+    #[test]
+    fn removes_columns_continuing_merged_values() {
+        let range = sheet(&[
+            &["День", "Час", "Дисципліна", "", "Група"],
+            &["Понеділок", "08:30-09:50", "Алгебра", "", "1"],
+        ]);
+        let range = unmerge(range, &[region((0, 2), (0, 3)), region((1, 2), (1, 3))]);
+
+        assert_eq!(range.width(), 4);
+        assert_eq!(text(&range, (1, 3)), "1");
+    }
+
+    #[test]
+    fn finds_ods_merged_cells() {
+        let xml = r#"<office:document-content><office:body><office:spreadsheet>
+            <table:table table:name="Аркуш1">
+                <table:table-column table:number-columns-repeated="6"/>
+                <table:table-row table:number-rows-repeated="2"><table:table-cell table:number-columns-repeated="6"/></table:table-row>
+                <table:table-row>
+                    <table:table-cell table:number-rows-spanned="2"><text:p>Понеділок</text:p></table:table-cell>
+                    <table:table-cell table:number-columns-repeated="2"/>
+                    <table:table-cell table:number-columns-spanned="2"><text:p>Алгебра</text:p></table:table-cell>
+                    <table:covered-table-cell/>
+                </table:table-row>
+                <table:table-row><table:covered-table-cell/></table:table-row>
+            </table:table>
+            <table:table table:name="Аркуш2"><table:table-row><table:table-cell/></table:table-row></table:table>
+        </office:spreadsheet></office:body></office:document-content>"#;
+        let regions = ods_merged_cells(xml.as_bytes()).unwrap();
+
+        assert_eq!(regions["Аркуш1"], vec![region((2, 0), (3, 0)), region((2, 3), (2, 4))]);
+        assert!(!regions.contains_key("Аркуш2"));
+    }
+
+    #[test]
+    fn detects_formats_by_extension() {
+        assert_eq!(SpreadsheetFormat::from_extension(Path::new("ФІ.xlsx")), Some(SpreadsheetFormat::Workbook));
+        assert_eq!(SpreadsheetFormat::from_extension(Path::new("ФІ.XLS")), Some(SpreadsheetFormat::Workbook));
+        assert_eq!(SpreadsheetFormat::from_extension(Path::new("ФІ.ods")), Some(SpreadsheetFormat::Workbook));
+        assert_eq!(SpreadsheetFormat::from_extension(Path::new("ФІ.Csv")), Some(SpreadsheetFormat::Csv));
+        assert_eq!(SpreadsheetFormat::from_extension(Path::new("ФІ.json")), None);
+        assert_eq!(SpreadsheetFormat::from_extension(Path::new("ФІ")), None);
+    }
+
+    #[test]
+    fn detects_formats_by_magic_bytes() {
+        assert_eq!(SpreadsheetFormat::from_magic(b"PK\x03\x04\x14\x00"), SpreadsheetFormat::Workbook);
+        assert_eq!(SpreadsheetFormat::from_magic(&[0xD0, 0xCF, 0x11, 0xE0, 0xA1]), SpreadsheetFormat::Workbook);
+        assert_eq!(SpreadsheetFormat::from_magic("День;Час".as_bytes()), SpreadsheetFormat::Csv);
+        assert_eq!(SpreadsheetFormat::from_magic(b"PK"), SpreadsheetFormat::Csv);
+        assert_eq!(SpreadsheetFormat::from_magic(b""), SpreadsheetFormat::Csv);
+    }
+
+    #[test]
+    fn detects_formats_of_files_without_extensions_by_content() {
+        let dir = std::env::temp_dir().join(format!("naukma_schedule_{}_magic", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (workbook, csv) = (dir.join("workbook"), dir.join("csv"));
+        std::fs::write(&workbook, b"PK\x03\x04").unwrap();
+        std::fs::write(&csv, "День;Час").unwrap();
+
+        assert_eq!(SpreadsheetFormat::detect(&workbook).unwrap(), SpreadsheetFormat::Workbook);
+        assert_eq!(SpreadsheetFormat::detect(&csv).unwrap(), SpreadsheetFormat::Csv);
+    }
+
+    #[test]
+    fn reads_csv_with_either_delimiter() {
+        for content in [
+            "День;Час;Дисципліна\nПонеділок;08:30-09:50;Алгебра, геометрія\n",
+            "День,Час,Дисципліна\nПонеділок,08:30-09:50,\"Алгебра, геометрія\"\n",
+        ] {
+            let range = read_csv(content).unwrap();
+
+            assert_eq!(range.get_size(), (2, COLUMNS));
+            assert_eq!(text(&range, (1, 2)), "Алгебра, геометрія");
+            assert_eq!(range.get_value((1, 3)), Some(&Data::Empty));
+        }
+    }
+
+    #[test]
+    fn guesses_csv_delimiters_from_the_header_below_title_rows() {
+        let range = read_csv("Факультет інформатики, Економіка\nДень;Час;Дисципліна\nПонеділок;08:30-09:50;Алгебра\n").unwrap();
+
+        assert_eq!(text(&range, (0, 0)), "Факультет інформатики, Економіка");
+        assert_eq!(header_row(&range), Some(1));
+        assert_eq!(text(&range, (2, 2)), "Алгебра");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn directory_link_cycles_are_searched_once() {
+        let dir = std::env::temp_dir().join(format!("naukma_schedule_{}_link_cycle", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("2024")).unwrap();
+        std::fs::write(dir.join("2024").join("ФІ.csv"), "").unwrap();
+        std::os::unix::fs::symlink(&dir, dir.join("2024").join("all")).unwrap();
+
+        assert_eq!(discover_spreadsheets(&dir).unwrap(), [dir.join("2024").join("ФІ.csv")]);
+    }
+}
@@ -0,0 +1,504 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    str::FromStr,
+};
+
+use serde::Serialize;
+
+use crate::{
+    group::*,
+    schedule::*,
+    campus::Campus,
+    exam::{Exam, Date},
+    error::ScheduleError,
+};
+
+/// Represents a single lesson occurrence in a flattened timetable,
+/// referencing the schedule it was taken from.
+#[derive(Serialize, Clone, Copy, Debug)]
+pub struct Lesson<'a> {
+    /// The name of the faculty teaching the lesson.
+    #[serde(rename = "Факультет")]
+    pub faculty: &'a str,
+    /// The speciality the lesson belongs to.
+    #[serde(rename = "Спеціальність")]
+    pub speciality: SpecialityName,
+    /// The full discipline name, as written in the spreadsheet.
+    #[serde(rename = "Дисципліна")]
+    pub discipline: &'a str,
+    /// The lesson group with its time, weeks and auditorium.
+    #[serde(flatten)]
+    pub group: &'a Group,
+}
+
+impl Lesson<'_> {
+    /// Returns the date of the lesson in the given studying week.
+    ///
+    /// # Arguments
+    ///
+    /// * `semester_start`: Any day of the first studying week.
+    /// * `week`: The studying week number, starting from 1.
+    pub fn date(&self, semester_start: Date, week: u8) -> Date {
+        let days = i64::from(week.saturating_sub(1)) * 7 + self.group.day as i64;
+
+        semester_start.monday().add_days(days)
+    }
+}
+
+/// Represents a personal choice of a discipline and its classes groups.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DisciplineChoice {
+    /// A part of the discipline name to search for.
+    pub discipline: String,
+    /// Chosen group numbers. If empty, every group of the discipline is chosen.
+    pub groups: Vec<GroupNumber>,
+}
+
+impl DisciplineChoice {
+    /// Checks whether a lesson of the given discipline is chosen.
+    ///
+    /// Lessons intended for each group (e.g. lections) are always chosen.
+    pub fn matches(&self, discipline: &str, lesson: &LessonType) -> bool {
+        if !discipline.contains(&self.discipline) {
+            return false;
+        }
+
+        self.groups.is_empty() || self.groups.iter().any(|number| lesson.includes(*number))
+    }
+}
+
+impl FromStr for DisciplineChoice {
+    type Err = ScheduleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.rsplit_once(':') {
+            Some((discipline, groups)) => {
+                let mut numbers = vec![];
+
+                for number in groups.split(',') {
+                    numbers.push(number.trim().parse::<GroupNumber>()
+                        .map_err(|_| ScheduleError::InvalidLessonType(number.to_owned()))?);
+                }
+
+                Ok(DisciplineChoice { discipline: discipline.trim().to_owned(), groups: numbers })
+            },
+            None => Ok(DisciplineChoice { discipline: s.trim().to_owned(), groups: vec![] }),
+        }
+    }
+}
+
+/// Represents a selection of lessons to be put into a timetable.
+///
+/// Empty fields do not restrict the selection.
+#[derive(Default, Clone, Debug)]
+pub struct Selection {
+    /// The name of the faculty to select.
+    pub faculty: Option<String>,
+    /// The speciality to select.
+    pub speciality: Option<SpecialityName>,
+    /// The studying week to select.
+    pub week: Option<u8>,
+    /// Personally chosen disciplines and groups.
+    pub disciplines: Vec<DisciplineChoice>,
+}
+
+impl Selection {
+    /// Checks whether a lesson fits into the selection.
+    pub fn matches(&self, lesson: &Lesson) -> bool {
+        if let Some(faculty) = &self.faculty {
+            if lesson.faculty != faculty {
+                return false;
+            }
+        }
+
+        if let Some(speciality) = self.speciality {
+            if lesson.speciality != speciality {
+                return false;
+            }
+        }
+
+        if let Some(week) = self.week {
+            if !lesson.group.weeks.contains(week) {
+                return false;
+            }
+        }
+
+        self.disciplines.is_empty() || self.disciplines
+            .iter()
+            .any(|choice| choice.matches(lesson.discipline, &lesson.group.name))
+    }
+
+    /// Checks whether an exam of a faculty fits into the selection.
+    /// The week does not restrict exams.
+    pub fn matches_exam(&self, faculty: &str, exam: &Exam) -> bool {
+        if self.faculty.as_ref().is_some_and(|f| f != faculty) {
+            return false;
+        }
+
+        if self.speciality.is_some_and(|s| !exam.specialities.contains(&s)) {
+            return false;
+        }
+
+        self.disciplines.is_empty() || self.disciplines
+            .iter()
+            .any(|choice| choice.matches(&exam.discipline, &exam.group))
+    }
+}
+
+/// Represents a flat list of lessons selected from a `Schedule`,
+/// suitable for rendering as a weekly grid.
+#[derive(Clone, Debug)]
+pub struct Timetable<'a> {
+    /// Selected lessons, sorted by day and time.
+    pub lessons: Vec<Lesson<'a>>,
+}
+
+impl<'a> Timetable<'a> {
+    /// Creates a new `Timetable` containing the lessons of the schedule matching the selection.
+    ///
+    /// Lessons shared by several specialities (e.g. common lections) are included once.
+    pub fn new(schedule: &'a Schedule, selection: &Selection) -> Self {
+        let mut lessons: Vec<Lesson<'a>> = vec![];
+
+        for faculty in &schedule.faculties {
+            for (speciality, spec) in &faculty.specialities {
+                for (discipline, disc) in &spec.disciplines {
+                    for group in &disc.groups {
+                        let lesson = Lesson {
+                            faculty: &faculty.name,
+                            speciality: *speciality,
+                            discipline,
+                            group,
+                        };
+
+                        if !selection.matches(&lesson) {
+                            continue;
+                        }
+
+                        let duplicate = lessons.iter().any(|l| {
+                            l.faculty == lesson.faculty
+                                && l.discipline == lesson.discipline
+                                && l.group == lesson.group
+                        });
+
+                        if !duplicate {
+                            lessons.push(lesson);
+                        }
+                    }
+                }
+            }
+        }
+
+        Timetable::from_lessons(lessons)
+    }
+
+    /// Creates a new `Timetable` like `Timetable::new`, but chooses a single seminar group
+    /// for every chosen discipline without explicitly chosen groups.
+    ///
+    /// Groups are chosen to prefer compact days: fewer studying days, shorter gaps between lessons,
+    /// no overlapping lessons and enough time to walk between buildings.
+    ///
+    /// # Arguments
+    ///
+    /// * `schedule`: The schedule to take lessons from.
+    /// * `selection`: The lessons to put into the timetable.
+    /// * `campus`: The campus map used to estimate walking times.
+    ///
+    /// # Returns
+    ///
+    /// The compact `Timetable`.
+    pub fn compact(schedule: &'a Schedule, selection: &Selection, campus: &Campus) -> Self {
+        let mut fixed: Vec<Lesson<'a>> = vec![];
+        let mut open: BTreeMap<(&'a str, &'a str), Vec<Lesson<'a>>> = BTreeMap::new();
+
+        for lesson in Timetable::new(schedule, selection).lessons {
+            let is_open = !lesson.group.name.is_for_each_group()
+                && selection.disciplines.iter().any(|c| c.groups.is_empty() && c.matches(lesson.discipline, &lesson.group.name))
+                && !selection.disciplines.iter().any(|c| !c.groups.is_empty() && c.matches(lesson.discipline, &lesson.group.name));
+
+            if is_open {
+                open.entry((lesson.faculty, lesson.discipline)).or_default().push(lesson);
+            } else {
+                fixed.push(lesson);
+            }
+        }
+
+        let mut choices: Vec<(Vec<GroupNumber>, Vec<Lesson<'a>>)> = open
+            .into_values()
+            .map(|lessons| {
+                let mut numbers: Vec<GroupNumber> = lessons
+                    .iter()
+                    .flat_map(|l| l.group.name.groups.iter().copied())
+                    .collect();
+                numbers.sort_unstable();
+                numbers.dedup();
+
+                (numbers, lessons)
+            })
+            .collect();
+
+        // Disciplines with fewer groups leave less freedom, so they are placed first
+        choices.sort_by_key(|(numbers, _)| numbers.len());
+
+        for (numbers, lessons) in choices {
+            let best = numbers
+                .iter()
+                .map(|number| {
+                    let mut candidate = fixed.clone();
+                    candidate.extend(lessons.iter().filter(|l| l.group.name.includes(*number)));
+
+                    let cost = Timetable::from_lessons(candidate.clone()).cost(campus);
+                    (cost, candidate)
+                })
+                .min_by_key(|(cost, _)| *cost);
+
+            if let Some((_, candidate)) = best {
+                fixed = candidate;
+            }
+        }
+
+        Timetable::from_lessons(fixed)
+    }
+
+    /// Creates a new `Timetable` from the lessons, sorting them by day and time.
+    fn from_lessons(mut lessons: Vec<Lesson<'a>>) -> Self {
+        lessons.sort_by(|a, b| {
+            (a.group.day, a.group.time, a.discipline)
+                .cmp(&(b.group.day, b.group.time, b.discipline))
+        });
+
+        Timetable { lessons }
+    }
+
+    /// Estimates how inconvenient the timetable is, summing up studying days,
+    /// gaps between lessons, overlapping lessons and too short breaks for every week.
+    fn cost(&self, campus: &Campus) -> u32 {
+        /// The cost of an additional studying day, in minutes of gaps.
+        const DAY: u32 = 180;
+        /// The cost of overlapping lessons.
+        const OVERLAP: u32 = 1000;
+        /// The cost of a break too short to walk between buildings.
+        const TIGHT_BREAK: u32 = 60;
+
+        let mut cost = 0;
+
+        for week in self.weeks() {
+            for day in Day::ALL {
+                let lessons: Vec<&Lesson> = self
+                    .at_day(day)
+                    .filter(|l| l.group.weeks.contains(week))
+                    .collect();
+
+                for (index, lesson) in lessons.iter().enumerate() {
+                    match lessons.get(index + 1) {
+                        Some(next) => match lesson.group.time.break_until(&next.group.time) {
+                            Some(minutes) => cost += u32::from(minutes),
+                            None => cost += OVERLAP,
+                        },
+                        None => cost += DAY,
+                    }
+                }
+            }
+        }
+
+        cost + self.tight_breaks(campus).len() as u32 * TIGHT_BREAK
+    }
+
+    /// Returns the distinct lesson times of the timetable, in ascending order.
+    pub fn slots(&self) -> Vec<LessonTime> {
+        let mut slots: Vec<LessonTime> = self.lessons.iter().map(|l| l.group.time).collect();
+        slots.sort_unstable();
+        slots.dedup();
+        slots
+    }
+
+    /// Returns every studying week in which at least one lesson takes place.
+    pub fn weeks(&self) -> Vec<u8> {
+        let mut weeks: Vec<u8> = self.lessons
+            .iter()
+            .flat_map(|l| l.group.weeks.numbers())
+            .collect();
+        weeks.sort_unstable();
+        weeks.dedup();
+        weeks
+    }
+
+    /// Returns the lessons taking place on the given day.
+    pub fn at_day(&self, day: Day) -> impl Iterator<Item = &Lesson<'a>> {
+        self.lessons.iter().filter(move |l| l.group.day == day)
+    }
+
+    /// Finds consecutive lessons with a break shorter than the walk between their buildings.
+    ///
+    /// Lessons are consecutive if they take place on the same day and share at least one week.
+    /// Distance lessons and buildings missing from the campus map are not checked.
+    ///
+    /// # Arguments
+    ///
+    /// * `campus`: The campus map used to estimate walking times.
+    ///
+    /// # Returns
+    ///
+    /// A list of too short breaks, sorted by day and time.
+    pub fn tight_breaks(&self, campus: &Campus) -> Vec<TightBreak<'a>> {
+        let mut breaks = vec![];
+
+        for (index, first) in self.lessons.iter().enumerate() {
+            let next = self.lessons[index + 1..]
+                .iter()
+                .filter(|l| l.group.day == first.group.day && l.group.weeks.overlaps(&first.group.weeks))
+                .filter_map(|l| Some((first.group.time.break_until(&l.group.time)?, l)))
+                .min_by_key(|(minutes, _)| *minutes);
+
+            let Some((break_minutes, second)) = next else {
+                continue;
+            };
+
+            let walk = campus.walking_minutes_between(&first.group.auditorium, &second.group.auditorium);
+            if let Some(walk_minutes) = walk.filter(|walk| *walk > break_minutes) {
+                breaks.push(TightBreak { from: *first, to: *second, break_minutes, walk_minutes });
+            }
+        }
+
+        breaks
+    }
+
+    /// Finds lessons taking place at overlapping times on the same day and weeks.
+    ///
+    /// Alternative seminar groups of the same discipline are not reported, since only one of them is attended.
+    pub fn clashes(&self) -> Vec<Clash<'a>> {
+        let mut clashes = vec![];
+
+        for (index, first) in self.lessons.iter().enumerate() {
+            for second in &self.lessons[index + 1..] {
+                let alternatives = first.faculty == second.faculty
+                    && first.discipline == second.discipline
+                    && !first.group.name.is_for_each_group()
+                    && !second.group.name.is_for_each_group();
+
+                let overlapping = first.group.day == second.group.day
+                    && first.group.time.from < second.group.time.to
+                    && second.group.time.from < first.group.time.to
+                    && first.group.weeks.overlaps(&second.group.weeks);
+
+                if overlapping && !alternatives {
+                    clashes.push(Clash { first: *first, second: *second });
+                }
+            }
+        }
+
+        clashes
+    }
+
+    /// Finds the first lesson starting at or after the given moment.
+    ///
+    /// # Arguments
+    ///
+    /// * `semester_start`: Any day of the first studying week.
+    /// * `date`: The current date.
+    /// * `time`: The current time.
+    ///
+    /// # Returns
+    ///
+    /// The date and the lesson, or `None` if no lessons are left in the semester.
+    pub fn next_lesson(&self, semester_start: Date, date: Date, time: Time) -> Option<(Date, &Lesson<'a>)> {
+        self.lessons
+            .iter()
+            .flat_map(|lesson| {
+                lesson.group.weeks
+                    .numbers()
+                    .into_iter()
+                    .map(move |week| (lesson.date(semester_start, week), lesson))
+            })
+            .filter(|(day, lesson)| (*day, lesson.group.time.from) >= (date, time))
+            .min_by_key(|(day, lesson)| (*day, lesson.group.time))
+    }
+
+    /// Returns the lessons taking place on the given day and time.
+    pub fn at(&self, day: Day, time: LessonTime) -> impl Iterator<Item = &Lesson<'a>> {
+        self.lessons
+            .iter()
+            .filter(move |l| l.group.day == day && l.group.time == time)
+    }
+}
+
+/// Represents two lessons of a timetable taking place at the same time.
+#[derive(Clone, Copy, Debug)]
+pub struct Clash<'a> {
+    /// The earlier lesson.
+    pub first: Lesson<'a>,
+    /// The later lesson.
+    pub second: Lesson<'a>,
+}
+
+impl Display for Clash<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: `{}` ({}, {}) overlaps with `{}` ({}, {})",
+            self.first.group.day,
+            self.first.discipline,
+            self.first.group.time,
+            self.first.group.name,
+            self.second.discipline,
+            self.second.group.time,
+            self.second.group.name,
+        )
+    }
+}
+
+/// Represents a break between consecutive lessons which is shorter than the walk
+/// between their buildings.
+#[derive(Clone, Copy, Debug)]
+pub struct TightBreak<'a> {
+    /// The earlier lesson.
+    pub from: Lesson<'a>,
+    /// The later lesson.
+    pub to: Lesson<'a>,
+    /// The break between the lessons in minutes.
+    pub break_minutes: u16,
+    /// The estimated walking time between the buildings in minutes.
+    pub walk_minutes: u16,
+}
+
+impl Display for TightBreak<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {}-minute break between `{}` ({}, {}) and `{}` ({}, {}), but the walk takes {} minutes",
+            self.from.group.day,
+            self.break_minutes,
+            self.from.discipline,
+            self.from.group.time,
+            self.from.group.auditorium,
+            self.to.discipline,
+            self.to.group.time,
+            self.to.group.auditorium,
+            self.walk_minutes,
+        )
+    }
+}
+
+/// Represents an exam selected from a `Schedule`, with the faculty it belongs to.
+#[derive(Clone, Copy, Debug)]
+pub struct ExamEntry<'a> {
+    /// The name of the faculty holding the exam.
+    pub faculty: &'a str,
+    /// The exam with its date, time and auditorium.
+    pub exam: &'a Exam,
+}
+
+impl<'a> ExamEntry<'a> {
+    /// Selects the exams of the schedule matching the selection, sorted by date and time.
+    pub fn select(schedule: &'a Schedule, selection: &Selection) -> Vec<Self> {
+        let mut entries: Vec<ExamEntry<'a>> = schedule.exam_sessions
+            .iter()
+            .flat_map(|session| session.exams.iter().map(|exam| ExamEntry { faculty: &session.faculty, exam }))
+            .filter(|entry| selection.matches_exam(entry.faculty, entry.exam))
+            .collect();
+
+        entries.sort_by_key(|entry| (entry.exam.date, entry.exam.time));
+        entries
+    }
+}
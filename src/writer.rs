@@ -0,0 +1,244 @@
+use std::path::{Path, PathBuf};
+use rust_xlsxwriter::{Workbook, Worksheet, Format, FormatAlign, FormatBorder};
+
+use crate::{
+    group::Group,
+    schedule::*,
+    error::ScheduleResult,
+};
+
+/// Column headers of a canonical worksheet.
+pub const HEADERS: [&str; 6] = ["День", "Час", "Дисципліна", "Група", "Тижні", "Аудиторія"];
+
+/// Writes a canonical workbook for every faculty of the schedule into a directory.
+///
+/// Workbooks are named after faculties, so they can be parsed back with `Faculty::new`.
+/// Faculties with the same name (e.g. parsed from a file per speciality) are written into one workbook.
+///
+/// # Arguments
+///
+/// * `schedule`: The schedule to write.
+/// * `directory`: The directory to write workbooks to.
+///
+/// # Returns
+///
+/// A `Result` containing the paths of the written workbooks, or an error if writing fails.
+pub fn write_schedule(schedule: &Schedule, directory: &Path) -> ScheduleResult<Vec<PathBuf>> {
+    let mut faculties: Vec<Faculty> = vec![];
+
+    for faculty in &schedule.faculties {
+        match faculties.iter_mut().find(|other| other.name == faculty.name) {
+            Some(other) => merge_specialities(other, faculty),
+            None => faculties.push(faculty.clone()),
+        }
+    }
+
+    let mut paths = vec![];
+
+    for faculty in &faculties {
+        let path = directory.join(format!("{}.xlsx", faculty.name));
+        write_faculty(faculty, &path)?;
+        paths.push(path);
+    }
+
+    Ok(paths)
+}
+
+/// Adds the specialities of a faculty with the same name, appending the lessons of shared disciplines.
+fn merge_specialities(faculty: &mut Faculty, other: &Faculty) {
+    for (name, speciality) in &other.specialities {
+        let disciplines = &mut faculty.specialities
+            .entry(*name)
+            .or_insert_with(|| Speciality { id: speciality.id.clone(), disciplines: Disciplines::new() })
+            .disciplines;
+
+        for (name, discipline) in &speciality.disciplines {
+            disciplines
+                .entry(name.clone())
+                .or_insert_with(|| Discipline { id: discipline.id.clone(), groups: vec![] })
+                .groups
+                .extend(discipline.groups.iter().cloned());
+        }
+    }
+}
+
+/// Writes a canonical workbook of a faculty, with a worksheet per speciality.
+///
+/// Worksheets are named after speciality abbreviations (see `SpecialityName::abbreviation`)
+/// and contain the `День | Час | Дисципліна | Група | Тижні | Аудиторія` columns,
+/// lessons sorted by day and time and merged day cells.
+///
+/// # Arguments
+///
+/// * `faculty`: The faculty to write.
+/// * `path`: The path of the workbook to write.
+///
+/// # Returns
+///
+/// A `Result` indicating success or failure of the writing.
+pub fn write_faculty(faculty: &Faculty, path: &Path) -> ScheduleResult<()> {
+    let mut workbook = Workbook::new();
+
+    for (name, speciality) in &faculty.specialities {
+        let worksheet = workbook.add_worksheet();
+        worksheet.set_name(name.abbreviation())?;
+        write_speciality(worksheet, speciality)?;
+    }
+
+    workbook.save(path)?;
+
+    Ok(())
+}
+
+/// Writes lessons of a speciality into a worksheet.
+fn write_speciality(worksheet: &mut Worksheet, speciality: &Speciality) -> ScheduleResult<()> {
+    let header = Format::new().set_bold().set_border(FormatBorder::Thin);
+    let cell = Format::new().set_border(FormatBorder::Thin).set_text_wrap();
+    let day_cell = cell.clone().set_align(FormatAlign::VerticalCenter);
+
+    for (column, title) in HEADERS.iter().enumerate() {
+        worksheet.write_string_with_format(0, column as u16, *title, &header)?;
+    }
+
+    for (column, width) in [14, 12, 60, 10, 16, 14].into_iter().enumerate() {
+        worksheet.set_column_width(column as u16, width)?;
+    }
+
+    let mut rows: Vec<(&String, &Group)> = speciality.disciplines
+        .iter()
+        .flat_map(|(name, discipline)| discipline.groups.iter().map(move |g| (name, g)))
+        .collect();
+    rows.sort_by(|(a_name, a), (b_name, b)| {
+        (a.day, a.time, *a_name).cmp(&(b.day, b.time, *b_name))
+    });
+
+    let mut first_row_of_day = 1;
+
+    for (index, (discipline, group)) in rows.iter().enumerate() {
+        let row = index as u32 + 1;
+
+        worksheet.write_string_with_format(row, 1, group.time.to_string(), &cell)?;
+        worksheet.write_string_with_format(row, 2, *discipline, &cell)?;
+        worksheet.write_string_with_format(row, 3, group.name.to_string(), &cell)?;
+        worksheet.write_string_with_format(row, 4, group.weeks.to_string(), &cell)?;
+        worksheet.write_string_with_format(row, 5, group.auditorium.to_string(), &cell)?;
+
+        // Merge day cells once the last lesson of the day is written
+        let last_of_day = rows
+            .get(index + 1)
+            .is_none_or(|(_, next)| next.day != group.day);
+
+        if last_of_day {
+            let day = group.day.to_string();
+
+            if first_row_of_day == row {
+                worksheet.write_string_with_format(row, 0, day, &day_cell)?;
+            } else {
+                worksheet.merge_range(first_row_of_day, 0, row, 0, &day, &day_cell)?;
+            }
+
+            first_row_of_day = row + 1;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    /// Lessons of every kind of weeks and auditoriums, in the columns below the header.
+    const ROWS: &str = "\
+        Понеділок;08:30-09:50;Алгебра;лекція;1-13;КМЦ\n\
+        ;10:00-11:20;Алгебра;1;непарні;3-205\n\
+        ;10:00-11:20;Алгебра;2;2,4,6-8;online (Zoom)\n\
+        Середа;11:40-13:00;Економіка праці;практ. 1-2;1-7;6-204 + Д\n\
+        Середа;13:30-14:50;Економіка праці;лекція;8;тба\n";
+
+    /// Creates an empty temporary directory for a test.
+    fn temp_dir(test: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("naukma_schedule_{}_{test}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Parses a faculty of the Економіка speciality from a CSV file with the lesson rows.
+    fn faculty(dir: &Path, name: &str) -> Faculty {
+        let path = dir.join(format!("{name}.Економіка.csv"));
+        fs::write(&path, format!("{}\n{ROWS}", HEADERS.join(";"))).unwrap();
+
+        Faculty::new(&path).unwrap()
+    }
+
+    /// Writes the faculty into the directory and parses the workbook back.
+    fn round_trip(faculty: &Faculty, dir: &Path) -> Faculty {
+        let schedule = Schedule { faculties: vec![faculty.clone()], ..Default::default() };
+        let paths = write_schedule(&schedule, dir).unwrap();
+        assert_eq!(paths.len(), 1);
+
+        Faculty::new(&paths[0]).unwrap()
+    }
+
+    /// Serializes a faculty for comparison.
+    fn json(faculty: &Faculty) -> String {
+        serde_json::to_string_pretty(faculty).unwrap()
+    }
+
+    #[test]
+    fn written_faculties_are_parsed_back_the_same() {
+        let dir = temp_dir("round_trip");
+        let faculty = faculty(&dir, "Факультет економічних наук");
+
+        assert_eq!(json(&round_trip(&faculty, &dir)), json(&faculty));
+    }
+
+    #[test]
+    fn specialities_without_lessons_are_parsed_back() {
+        let dir = temp_dir("empty_speciality");
+        let mut faculty = faculty(&dir, "Факультет економічних наук");
+        faculty.specialities.insert(SpecialityName::Finances, Speciality { id: String::new(), disciplines: Disciplines::new() });
+        faculty.assign_ids();
+
+        assert_eq!(json(&round_trip(&faculty, &dir)), json(&faculty));
+    }
+
+    #[test]
+    fn faculty_names_with_dots_are_parsed_back() {
+        let dir = temp_dir("dotted_name");
+        let faculty = faculty(&dir, "Ф.Е.Н.");
+        assert_eq!(faculty.name, "Ф.Е.Н.");
+
+        assert_eq!(json(&round_trip(&faculty, &dir)), json(&faculty));
+    }
+
+    #[test]
+    fn faculties_of_the_same_name_share_a_workbook() {
+        let dir = temp_dir("same_name");
+        let economics = faculty(&dir, "Факультет економічних наук");
+        let mut finances = economics.clone();
+        let speciality = finances.specialities.remove(&SpecialityName::Economics).unwrap();
+        finances.specialities.insert(SpecialityName::Finances, speciality);
+
+        let schedule = Schedule { faculties: vec![economics, finances], ..Default::default() };
+        let paths = write_schedule(&schedule, &dir).unwrap();
+        let parsed = Faculty::new(&paths[0]).unwrap();
+
+        assert_eq!(paths.len(), 1);
+        assert_eq!(parsed.specialities.keys().copied().collect::<Vec<_>>(), [SpecialityName::Economics, SpecialityName::Finances]);
+    }
+
+    #[test]
+    fn sheet_names_are_valid_and_distinct() {
+        let mut names: Vec<&str> = SpecialityName::ALL.iter().map(SpecialityName::abbreviation).collect();
+
+        // Excel limits sheet names to 31 characters, which must stay distinct
+        assert!(names.iter().all(|name| !name.is_empty() && name.chars().count() <= 31));
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), SpecialityName::ALL.len());
+    }
+}